@@ -5,15 +5,19 @@ use std::{
     path::PathBuf,
 };
 use topos_lib::{
-    data::genres::Genres,
+    data::{data::BibleData, genres::Genres, locale::Locale, versification::Versification},
     filter::{
         filter::{BibleFilter, IsFilter, Operation},
         filters::{book::BookFilter, genre::GenreFilter, testament::TestamentFilter},
     },
-    matcher::matcher::BibleMatcher,
+    matcher::{
+        citation::Role,
+        matcher::BibleMatcher,
+        options::{AnaphoraOptions, MatcherOptions, Strictness},
+    },
 };
 
-use crate::outputs::OutputMode;
+use crate::{commands::Command, groups::GroupBy, outputs::OutputMode};
 
 /**
 - By positively specifying a testament/genre/book, you will implicitly telling the program to exclude the remaining items in that category.
@@ -27,6 +31,9 @@ use crate::outputs::OutputMode;
     version = "0.1.0"
 )]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     #[clap(help = "The input can be a directory path, a file path, text, or stdin.")]
     pub input: Option<String>,
 
@@ -34,13 +41,15 @@ pub struct Args {
     #[clap(
         long = "testament",
         short = 't',
-        help = "Include books from a specific testament (old/new)"
+        help = "Include books from a specific testament (old/new/deuterocanon)",
+        global = true
     )]
     pub testaments: Option<Vec<TestamentFilter>>,
 
     #[clap(
         long = "exclude-testament",
-        help = "Exclude books from a specific testament"
+        help = "Exclude books from a specific testament",
+        global = true
     )]
     pub exclude_testaments: Option<Vec<TestamentFilter>>,
 
@@ -48,29 +57,36 @@ pub struct Args {
     #[clap(
         long = "genre",
         short = 'g',
-        help = "Include books of a specific genre (e.g. epistles, gospels)"
+        help = "Include books of a specific genre (e.g. epistles, gospels)",
+        global = true
     )]
     pub genres: Option<Vec<String>>,
 
-    #[clap(long = "exclude-genre", help = "Exclude books of a specific genre")]
+    #[clap(
+        long = "exclude-genre",
+        help = "Exclude books of a specific genre",
+        global = true
+    )]
     pub exclude_genres: Option<Vec<String>>,
 
     // Book filters
     #[clap(
         long = "book",
         short = 'b',
-        help = "Include specific books (e.g. John)"
+        help = "Include specific books (e.g. John)",
+        global = true
     )]
     pub books: Option<Vec<String>>,
 
-    #[clap(long = "exclude-book", help = "Exclude specific books")]
+    #[clap(long = "exclude-book", help = "Exclude specific books", global = true)]
     pub exclude_books: Option<Vec<String>>,
 
     // Verse range filters
     #[clap(
         long = "inside",
         short = 'i',
-        help = "Limit search to a verse range (e.g. John 1:2-3)"
+        help = "Limit search to a verse range (e.g. John 1:2-3)",
+        global = true
     )]
     pub inside: Option<Vec<String>>,
 
@@ -78,10 +94,106 @@ pub struct Args {
     #[clap(
         long = "outside",
         short = 'o',
-        help = "Forbid search from matching a verse range (e.g. John 3:4-5)"
+        help = "Forbid search from matching a verse range (e.g. John 3:4-5)",
+        global = true
     )]
     pub outside: Option<Vec<String>>,
 
+    #[clap(
+        long = "anaphora",
+        help = "Attach bare references (v. 16, vv. 18-20, 4:1, ibid.) to the most recently cited book",
+        global = true
+    )]
+    pub anaphora: bool,
+
+    #[clap(
+        long = "anaphora-window",
+        help = "How many bytes after a reference a bare reference may still attach to it",
+        default_value_t = 500,
+        global = true
+    )]
+    pub anaphora_window: usize,
+
+    #[clap(
+        long = "headings",
+        help = "Attach bare references to the book named by the Markdown/Org heading or front matter `book:` they are under",
+        global = true
+    )]
+    pub headings: bool,
+
+    #[clap(
+        long = "spoken",
+        help = "Also match spoken references (John chapter three, verses sixteen through eighteen), like in transcripts",
+        global = true
+    )]
+    pub spoken: bool,
+
+    #[clap(
+        long = "whole-books",
+        help = "Also match books named without a chapter (the book of Ruth), as the whole book",
+        global = true
+    )]
+    pub whole_books: bool,
+
+    #[clap(
+        long = "join-lines",
+        help = "Also match references split across lines (Romans\\n8:28, 1 Corin-\\nthians 13), like in hard-wrapped notes",
+        global = true
+    )]
+    pub join_lines: bool,
+
+    #[clap(
+        long = "canon",
+        help = "Which books to search by default (protestant, catholic, orthodox, all)",
+        global = true
+    )]
+    pub canon: Option<String>,
+
+    #[clap(
+        long = "locale",
+        short = 'l',
+        help = "Match book names in these languages (en, es, pt, de, fr, ko, zh); the first one listed wins when they share an abbreviation [default: en]",
+        value_delimiter = ',',
+        global = true
+    )]
+    pub locales: Option<Vec<Locale>>,
+
+    #[clap(
+        long = "output-locale",
+        help = "Write book names in this language instead of the first --locale",
+        global = true
+    )]
+    pub output_locale: Option<Locale>,
+
+    #[clap(
+        long = "versification",
+        help = "How the input numbers its verses (english, masoretic, septuagint, vulgate); matches are renumbered into English",
+        global = true
+    )]
+    pub versification: Option<Versification>,
+
+    #[clap(
+        long = "min-confidence",
+        help = "Drop matches that are less likely to be real references, from 0.0 (keep everything) to 1.0 (only references like `Rom. 8:28`)",
+        global = true
+    )]
+    pub min_confidence: Option<f32>,
+
+    #[clap(
+        long = "strictness",
+        help = "What to do with references to chapters or verses that don't exist, like `John 99:1` (lenient, flag, clamp, drop)",
+        global = true
+    )]
+    pub strictness: Option<Strictness>,
+
+    #[clap(
+        long = "role",
+        help = "Only keep references cited this way (primary, cross-reference, contrast, quotation); `cf.` and `see also` are cross-references",
+        value_delimiter = ',',
+        global = true
+    )]
+    pub roles: Option<Vec<Role>>,
+
     // TODO: actually implement this
     #[clap(long = "config", help = "Use a custom configuration file")]
     pub config: Option<PathBuf>,
@@ -99,6 +211,13 @@ pub struct Args {
     #[arg(value_enum)]
     pub mode: OutputMode,
 
+    #[clap(
+        long = "group-by",
+        help = "Group matches by passage, book, or chapter (in canonical order) instead of by file"
+    )]
+    #[arg(value_enum)]
+    pub group_by: Option<GroupBy>,
+
     #[clap(
         long = "verbose",
         short = 'v',
@@ -130,12 +249,54 @@ pub struct Args {
     pub after_context: Option<u64>,
 }
 
+impl Args {
+    /**
+    - Everything that changes which matches are found: the data, filters, and matcher options
+    - An index remembers it, so it is rebuilt when it is built with different ones
+    - Display-only settings (`--output-locale`, `--mode`) are left out
+    */
+    pub fn fingerprint(&self) -> String {
+        let filters = (
+            &self.testaments,
+            &self.exclude_testaments,
+            &self.genres,
+            &self.exclude_genres,
+            &self.books,
+            &self.exclude_books,
+            &self.inside,
+            &self.outside,
+        );
+        let data = (&self.canon, &self.locales, &self.versification);
+        let options = (
+            self.anaphora.then_some(self.anaphora_window),
+            self.headings,
+            self.spoken,
+            self.whole_books,
+            self.join_lines,
+            self.min_confidence,
+            &self.strictness,
+            &self.roles,
+        );
+        format!("{:?}", (filters, data, options))
+    }
+}
+
 impl TryFrom<Args> for BibleMatcher {
     type Error = Box<dyn std::error::Error>;
 
     fn try_from(args: Args) -> Result<Self, Self::Error> {
         // TODO: get alternate Bible/Genre data
-        let mut filter = BibleFilter::default();
+        let mut data = BibleData::default();
+        if let Some(canon) = &args.canon {
+            data = data.with_canon(canon)?;
+        }
+        if let Some(locales) = &args.locales {
+            data = data.with_locales(locales)?;
+        }
+        if let Some(locale) = args.output_locale {
+            data = data.with_display_locale(locale)?;
+        }
+        let mut filter = BibleFilter::new(data);
 
         if let Some(list) = args.testaments {
             filter.include_many(list);
@@ -173,6 +334,35 @@ impl TryFrom<Args> for BibleMatcher {
             }
         }
 
-        Ok(filter.create_matcher()?)
+        let mut options = MatcherOptions::default();
+        if args.anaphora {
+            options = options.with_anaphora(AnaphoraOptions::new(args.anaphora_window));
+        }
+        if args.headings {
+            options = options.with_headings();
+        }
+        if args.spoken {
+            options = options.with_spoken();
+        }
+        if args.whole_books {
+            options = options.with_whole_books();
+        }
+        if args.join_lines {
+            options = options.with_join_lines();
+        }
+        if let Some(min_confidence) = args.min_confidence {
+            options = options.with_min_confidence(min_confidence);
+        }
+        if let Some(versification) = args.versification {
+            options = options.with_versification(versification);
+        }
+        if let Some(strictness) = args.strictness {
+            options = options.with_strictness(strictness);
+        }
+        if let Some(roles) = args.roles {
+            options = options.with_roles(roles);
+        }
+
+        Ok(filter.create_matcher()?.with_options(options))
     }
 }
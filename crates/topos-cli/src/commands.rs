@@ -0,0 +1,17 @@
+use clap::Subcommand;
+
+#[cfg(feature = "sqlite")]
+use crate::export::ExportArgs;
+use crate::{digest::DigestArgs, index::IndexCommand};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Write a Markdown commentary per book/chapter from every cited passage's surrounding paragraph
+    Digest(DigestArgs),
+    /// Build or query an on-disk index of every match in a directory
+    #[command(subcommand)]
+    Index(IndexCommand),
+    /// Write every match, book, and genre to a SQLite database
+    #[cfg(feature = "sqlite")]
+    Export(ExportArgs),
+}
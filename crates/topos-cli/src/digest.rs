@@ -0,0 +1,247 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use topos_lib::{error::AnyResult, matcher::matcher::BibleMatcher, segments::segments::Passage};
+
+use crate::{
+    groups::{GroupBy, GroupKey},
+    inputs::InputType,
+    matches::PathMatches,
+    outputs::MatchRecord,
+};
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct DigestArgs {
+    #[clap(help = "The directory or file of notes to digest", default_value = ".")]
+    pub input: PathBuf,
+
+    #[clap(
+        long = "out",
+        short = 'O',
+        help = "The directory to write the digest into",
+        default_value = "digest"
+    )]
+    pub out: PathBuf,
+
+    #[clap(
+        long = "per",
+        help = "Write one document per book, chapter, or passage",
+        default_value = "book"
+    )]
+    #[arg(value_enum)]
+    pub per: GroupBy,
+}
+
+/// A citation, with the paragraph it was found in
+#[derive(Clone, Debug)]
+pub struct Excerpt {
+    pub psg: Passage,
+    pub record: MatchRecord,
+    pub paragraph: String,
+}
+
+/**
+- Every excerpt for a group goes into one document
+- Documents and the excerpts in them are in canonical order
+*/
+#[derive(Clone, Debug)]
+pub struct Digest {
+    groups: BTreeMap<GroupKey, Vec<Excerpt>>,
+}
+
+impl Digest {
+    pub fn new(
+        per: GroupBy,
+        matcher: &BibleMatcher,
+        results: impl Iterator<Item = AnyResult<PathMatches>>,
+    ) -> Self {
+        let mut groups: BTreeMap<GroupKey, Vec<Excerpt>> = BTreeMap::new();
+        for PathMatches { path, matches } in results.filter_map(Result::ok) {
+            // Only files can be linked back to
+            let Some(path) = path else {
+                continue;
+            };
+            let Ok(text) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let path = path.to_string_lossy().into_owned();
+            for m in matches {
+                let Some(record) = MatchRecord::new(matcher, &path, &m) else {
+                    continue;
+                };
+                let paragraph = surrounding_paragraph(&text, record.start_byte, record.end_byte);
+                let excerpt = Excerpt {
+                    psg: m.psg.clone(),
+                    record,
+                    paragraph: paragraph.to_string(),
+                };
+                for key in per.keys(matcher, &m) {
+                    groups.entry(key).or_default().push(excerpt.clone());
+                }
+            }
+        }
+
+        for excerpts in groups.values_mut() {
+            excerpts.sort_by(|a, b| {
+                let a_loc = (&a.record.path, a.record.line, a.record.column);
+                let b_loc = (&b.record.path, b.record.line, b.record.column);
+                a.psg.cmp(&b.psg).then(a_loc.cmp(&b_loc))
+            });
+        }
+
+        Self { groups }
+    }
+
+    /// Writes each document, and an `index.md` linking to them (in order)
+    pub fn write(&self, matcher: &BibleMatcher, out: &Path) -> AnyResult<()> {
+        fs::create_dir_all(out)?;
+
+        let mut index = String::from("# Digest\n\n");
+        for (key, excerpts) in self.groups.iter() {
+            let Some(title) = key.title(matcher) else {
+                continue;
+            };
+            let file_name = format!("{}.md", document_stem(key, &title));
+            index.push_str(&format!("- [{}](<{}>)\n", title, file_name));
+
+            let document = render_document(&title, excerpts, out);
+            fs::write(out.join(&file_name), document)?;
+        }
+        fs::write(out.join("index.md"), index)?;
+
+        Ok(())
+    }
+}
+
+impl DigestArgs {
+    pub fn run(self, matcher: &BibleMatcher) -> AnyResult<()> {
+        let input = match InputType::new(Some(self.input.to_string_lossy().into_owned())) {
+            InputType::TextInput(_) => Err(format!("{:?} is not a file or directory", self.input))?,
+            input => input,
+        };
+        let results = input.search(matcher.clone());
+        Digest::new(self.per, matcher, results).write(matcher, &self.out)
+    }
+}
+
+fn render_document(title: &str, excerpts: &[Excerpt], out: &Path) -> String {
+    let mut document = format!("# {}\n", title);
+    let mut prev: Option<&str> = None;
+    for Excerpt {
+        record, paragraph, ..
+    } in excerpts
+    {
+        // consecutive excerpts of the same passage share a heading
+        if prev != Some(record.passage.as_str()) {
+            document.push_str(&format!("\n## {}\n", record.passage));
+            prev = Some(record.passage.as_str());
+        }
+        document.push('\n');
+        for line in paragraph.lines() {
+            document.push_str(&format!("> {}\n", line.trim_end()));
+        }
+        document.push_str(&format!(
+            "\n[{}:{}](<{}#L{}>)\n",
+            record.path,
+            record.line,
+            relative_link(out, Path::new(&record.path)),
+            record.line
+        ));
+    }
+    document
+}
+
+/// - `01 Genesis`, `45 Romans 008`, ...
+/// - The book id and chapter are zero-padded so file listings sort canonically too
+fn document_stem(key: &GroupKey, title: &str) -> String {
+    let title = title.replace([':', '/', '\\'], ".");
+    match key {
+        GroupKey::Book(book) => format!("{:02} {}", **book, title),
+        GroupKey::Chapter(book, chapter) => {
+            let book_name = title
+                .trim_end_matches(|c: char| c.is_ascii_digit())
+                .trim_end();
+            format!("{:02} {} {:03}", **book, book_name, chapter)
+        }
+        GroupKey::Passage(psg) => format!("{:02} {}", *psg.book, title),
+    }
+}
+
+/**
+- The paragraph is every line around the match up to the nearest blank lines
+- Byte offsets come from the match location, so they always land on the right line
+*/
+pub fn surrounding_paragraph(text: &str, start: usize, end: usize) -> &str {
+    let is_blank = |line: &str| line.trim().is_empty();
+
+    let mut para_start = text[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    while para_start > 0 {
+        let prev_start = text[..para_start - 1]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        if is_blank(&text[prev_start..para_start - 1]) {
+            break;
+        }
+        para_start = prev_start;
+    }
+
+    let mut para_end = text[end..]
+        .find('\n')
+        .map(|i| end + i)
+        .unwrap_or(text.len());
+    while para_end < text.len() {
+        let next_end = text[para_end + 1..]
+            .find('\n')
+            .map(|i| para_end + 1 + i)
+            .unwrap_or(text.len());
+        if is_blank(&text[para_end + 1..next_end]) {
+            break;
+        }
+        para_end = next_end;
+    }
+
+    &text[para_start..para_end]
+}
+
+/// A link from inside `dir` to `target`, falling back to the absolute path
+fn relative_link(dir: &Path, target: &Path) -> String {
+    let (Ok(dir), Ok(target)) = (dir.canonicalize(), target.canonicalize()) else {
+        return target.to_string_lossy().into_owned();
+    };
+    let common = dir
+        .components()
+        .zip(target.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut link = PathBuf::new();
+    for _ in dir.components().skip(common) {
+        link.push("..");
+    }
+    for component in target.components().skip(common) {
+        link.push(component);
+    }
+    link.to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::surrounding_paragraph;
+
+    #[test]
+    fn paragraph_around_match() {
+        let text = "Intro\n\nFirst line\nsee John 3:16 here\nlast line\n\nOutro";
+        let start = text.find("John").unwrap();
+        let end = start + "John 3:16".len();
+        assert_eq!(
+            surrounding_paragraph(text, start, end),
+            "First line\nsee John 3:16 here\nlast line"
+        );
+
+        let text = "John 3:16";
+        assert_eq!(surrounding_paragraph(text, 0, text.len()), "John 3:16");
+    }
+}
@@ -0,0 +1,303 @@
+use std::path::PathBuf;
+
+use rusqlite::{Connection, params};
+use topos_lib::{
+    error::AnyResult,
+    matcher::matcher::BibleMatcher,
+    segments::{segment::Segment, verse_bounds::VerseBounds},
+};
+
+use crate::{inputs::InputType, matches::PathMatches};
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ExportArgs {
+    #[clap(help = "The SQLite database to write (its topos tables are replaced)")]
+    pub database: PathBuf,
+
+    #[clap(help = "The directory or file to search", default_value = ".")]
+    pub input: PathBuf,
+}
+
+/**
+- `matches` has one row per segment, so `John 3:16,18` is two rows sharing a `citation`
+- Every segment is stored as an inclusive range of verse ordinals (Genesis 1:1 is `0`), so
+overlap is just `start_ordinal <= :end AND end_ordinal >= :start`
+- Open-ended segments (like whole chapters) store their last verse as `end_verse`, and whole
+books are stored as their chapters
+- Where a segment ends is `NULL` only when the book has no chapter data to tell
+*/
+const SCHEMA: &str = r#"
+DROP TABLE IF EXISTS matches;
+DROP TABLE IF EXISTS files;
+DROP TABLE IF EXISTS genre_books;
+DROP TABLE IF EXISTS genres;
+DROP TABLE IF EXISTS books;
+
+CREATE TABLE books (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    abbreviation TEXT
+);
+
+CREATE TABLE genres (
+    id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL
+);
+
+CREATE TABLE genre_books (
+    genre_id INTEGER NOT NULL REFERENCES genres(id),
+    book_id INTEGER NOT NULL REFERENCES books(id),
+    PRIMARY KEY (genre_id, book_id)
+);
+
+CREATE TABLE files (
+    id INTEGER PRIMARY KEY,
+    path TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE matches (
+    id INTEGER PRIMARY KEY,
+    file_id INTEGER NOT NULL REFERENCES files(id),
+    citation INTEGER NOT NULL,
+    passage TEXT NOT NULL,
+    book_id INTEGER NOT NULL REFERENCES books(id),
+    start_chapter INTEGER NOT NULL,
+    start_verse INTEGER NOT NULL,
+    end_chapter INTEGER,
+    end_verse INTEGER,
+    start_ordinal INTEGER,
+    end_ordinal INTEGER,
+    start_byte INTEGER NOT NULL,
+    end_byte INTEGER NOT NULL,
+    start_line INTEGER NOT NULL,
+    start_column INTEGER NOT NULL,
+    end_line INTEGER NOT NULL,
+    end_column INTEGER NOT NULL
+);
+
+CREATE INDEX matches_by_ordinal ON matches(start_ordinal, end_ordinal);
+CREATE INDEX matches_by_file ON matches(file_id);
+"#;
+
+impl ExportArgs {
+    pub fn run(self, matcher: &BibleMatcher) -> AnyResult<()> {
+        let input = match InputType::new(Some(self.input.to_string_lossy().into_owned())) {
+            InputType::TextInput(_) => Err(format!("{:?} is not a file or directory", self.input))?,
+            input => input,
+        };
+
+        let mut conn = Connection::open(&self.database)?;
+        let tx = conn.transaction()?;
+        tx.execute_batch(SCHEMA)?;
+        write_data(&tx, matcher)?;
+
+        let mut count = 0;
+        for result in input.search(matcher.clone()) {
+            match result {
+                Ok(path_matches) => count += write_matches(&tx, matcher, path_matches)?,
+                Err(err) => eprintln!("Error: {}", err),
+            }
+        }
+        tx.commit()?;
+
+        eprintln!("Exported {} matches to {:?}", count, self.database);
+        Ok(())
+    }
+}
+
+/// The books and genres from [`BibleData`](topos_lib::data::data::BibleData), so matches can be joined on them
+fn write_data(conn: &Connection, matcher: &BibleMatcher) -> AnyResult<()> {
+    let data = matcher.data();
+
+    let mut insert_book =
+        conn.prepare("INSERT INTO books (id, name, abbreviation) VALUES (?1, ?2, ?3)")?;
+    for (id, name) in data.books().iter_ids_and_names() {
+        insert_book.execute(params![**id, name, data.books().get_abbrev(*id)])?;
+    }
+
+    let mut insert_genre = conn.prepare("INSERT INTO genres (id, title) VALUES (?1, ?2)")?;
+    let mut insert_genre_book =
+        conn.prepare("INSERT INTO genre_books (genre_id, book_id) VALUES (?1, ?2)")?;
+    for (key, genre) in data.genres().iter() {
+        insert_genre.execute(params![**key, genre.name()])?;
+        for book in genre.books() {
+            insert_genre_book.execute(params![**key, **book])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the number of citations written
+fn write_matches(
+    conn: &Connection,
+    matcher: &BibleMatcher,
+    PathMatches { path, matches }: PathMatches,
+) -> AnyResult<usize> {
+    let path = path
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    conn.execute("INSERT INTO files (path) VALUES (?1)", params![path])?;
+    let file_id = conn.last_insert_rowid();
+
+    let chapter_verses = matcher.data().chapter_verses();
+    let mut insert = conn.prepare_cached(
+        "INSERT INTO matches (
+            file_id, citation, passage, book_id,
+            start_chapter, start_verse, end_chapter, end_verse,
+            start_ordinal, end_ordinal,
+            start_byte, end_byte, start_line, start_column, end_line, end_column
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+    )?;
+
+    for (citation, m) in matches.iter().enumerate() {
+        let book = m.psg.book;
+        let Some(passage) = matcher.data().format_passage(&m.psg) else {
+            continue;
+        };
+        let location = &m.location;
+
+        // a whole book is stored as its chapters
+        let segments = match chapter_verses.get_chapter_verses(&book) {
+            Some(cv) => m.psg.segments.resolve_full_books(cv),
+            None => m.psg.segments.clone(),
+        };
+        for seg in segments.iter() {
+            // unless the book has no chapter data, so where it ends isn't known
+            let end_chapter = (!matches!(seg, Segment::FullBook(_))).then(|| seg.ending_chapter());
+            let end_verse = seg.ending_verse().or_else(|| {
+                chapter_verses
+                    .get_chapter_verses(&book)?
+                    .get_last_verse(end_chapter?)
+            });
+            let ordinals = chapter_verses.ordinal_range(book, seg);
+            insert.execute(params![
+                file_id,
+                citation,
+                passage,
+                *book,
+                seg.starting_chapter(),
+                seg.starting_verse(),
+                end_chapter,
+                end_verse,
+                ordinals.map(|o| o.start),
+                ordinals.map(|o| o.end),
+                location.bytes.start,
+                location.bytes.end,
+                location.start.line,
+                location.start.column,
+                location.end.line,
+                location.end.column,
+            ])?;
+        }
+    }
+
+    Ok(matches.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+    use topos_lib::matcher::matcher::BibleMatcher;
+
+    use super::{SCHEMA, write_data, write_matches};
+    use crate::matches::PathMatches;
+
+    type Row = (
+        usize,
+        String,
+        u8,
+        u8,
+        Option<u8>,
+        Option<u8>,
+        Option<u32>,
+        Option<u32>,
+    );
+
+    #[test]
+    fn export() {
+        let path = std::env::temp_dir().join(format!("topos-export-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let matcher = BibleMatcher::default();
+
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        write_data(&conn, &matcher).unwrap();
+        let text = "Genesis 1:1-3, John 3:16,18, Genesis 2:1, and Romans 8".to_string();
+        let count = write_matches(&conn, &matcher, PathMatches::from_text(text, &matcher)).unwrap();
+        assert_eq!(count, 4);
+
+        let rows: Vec<Row> = conn
+            .prepare(
+                "SELECT m.citation, b.name, m.start_chapter, m.start_verse, m.end_chapter,
+                    m.end_verse, m.start_ordinal, m.end_ordinal
+                FROM matches m JOIN books b ON b.id = m.book_id ORDER BY m.id",
+            )
+            .unwrap()
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                ))
+            })
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let john = |verse: u8| rows.iter().find(|r| r.1 == "John" && r.3 == verse).unwrap();
+        assert_eq!(
+            rows.iter().map(|r| (r.0, r.1.as_str())).collect::<Vec<_>>(),
+            vec![
+                (0, "Genesis"),
+                (1, "John"),
+                (1, "John"),
+                (2, "Genesis"),
+                (3, "Romans")
+            ]
+        );
+        // Genesis 1:1 is the first verse, and Genesis 1 has 31 of them
+        assert_eq!(
+            rows[0],
+            (
+                0,
+                "Genesis".to_string(),
+                1,
+                1,
+                Some(1),
+                Some(3),
+                Some(0),
+                Some(2)
+            )
+        );
+        assert_eq!(
+            rows[3],
+            (
+                2,
+                "Genesis".to_string(),
+                2,
+                1,
+                Some(2),
+                Some(1),
+                Some(31),
+                Some(31)
+            )
+        );
+        // one row per segment, each with its own range
+        assert_eq!(john(16).6, john(16).7);
+        assert_eq!(john(18).6.unwrap(), john(16).6.unwrap() + 2);
+        // a whole chapter ends on its last verse
+        assert_eq!(
+            (rows[4].2, rows[4].3, rows[4].4, rows[4].5),
+            (8, 1, Some(8), Some(39))
+        );
+
+        drop(conn);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use topos_lib::{
+    data::books::BookId,
+    error::AnyResult,
+    matcher::{instance::BibleMatch, matcher::BibleMatcher},
+    segments::{segment::Segment, segments::Passage, verse_bounds::VerseBounds},
+};
+
+use crate::{
+    matches::PathMatches,
+    outputs::{MatchRecord, OutputEntryFormat, OutputMode, csv_field, tsv_field},
+};
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum GroupBy {
+    #[value(alias = "p", help = "One entry per distinct passage")]
+    Passage,
+    #[value(alias = "b", help = "One entry per book")]
+    Book,
+    #[value(alias = "c", help = "One entry per chapter")]
+    Chapter,
+}
+
+/// - The derived ordering is canonical Bible order, because [`BookId`] and [`Passage`] are
+/// - A passage spanning several chapters is listed under each of them
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GroupKey {
+    Passage(Passage),
+    Book(BookId),
+    Chapter(BookId, u8),
+}
+
+impl GroupBy {
+    /// - A range into a later book (`Matthew 28 – Acts 1`) is under every book and chapter it
+    /// covers, not just the ones of where it starts
+    pub fn keys(&self, matcher: &BibleMatcher, m: &BibleMatch) -> Vec<GroupKey> {
+        let passages = match &m.range {
+            Some(range) => range.passages(matcher.data().chapter_verses()),
+            None => vec![m.psg.clone()],
+        };
+        match self {
+            GroupBy::Passage => vec![GroupKey::Passage(m.psg.clone())],
+            GroupBy::Book => passages
+                .iter()
+                .map(|psg| GroupKey::Book(psg.book))
+                .collect(),
+            GroupBy::Chapter => passages
+                .iter()
+                .flat_map(|psg| Self::chapter_keys(matcher, psg))
+                .collect(),
+        }
+    }
+
+    fn chapter_keys(matcher: &BibleMatcher, psg: &Passage) -> Vec<GroupKey> {
+        // a whole book is every one of its chapters
+        let segments = match matcher
+            .data()
+            .chapter_verses()
+            .get_chapter_verses(&psg.book)
+        {
+            Some(cv) => psg.segments.resolve_full_books(cv),
+            None => psg.segments.clone(),
+        };
+        let mut chapters: Vec<u8> = segments
+            .iter()
+            // unless the book has no chapter data, so its chapters aren't known
+            .filter(|seg| !matches!(seg, Segment::FullBook(_)))
+            .flat_map(|seg| seg.starting_chapter()..=seg.ending_chapter())
+            .collect();
+        chapters.sort();
+        chapters.dedup();
+        chapters
+            .into_iter()
+            .map(|chapter| GroupKey::Chapter(psg.book, chapter))
+            .collect()
+    }
+}
+
+impl GroupKey {
+    pub fn title(&self, matcher: &BibleMatcher) -> Option<String> {
+        let books = matcher.data().books();
+        Some(match self {
+            GroupKey::Passage(psg) => matcher.data().format_passage(psg)?,
+            GroupKey::Book(book) => books.get_name(*book)?.to_string(),
+            GroupKey::Chapter(book, chapter) => format!("{} {}", books.get_name(*book)?, chapter),
+        })
+    }
+}
+
+/**
+- A "Scripture index": every cited passage, in canonical order, with everywhere it was cited
+- Citations inside a group are sorted by file, then line, then column
+*/
+#[derive(Clone, Debug)]
+pub struct ScriptureIndex {
+    group_by: GroupBy,
+    groups: BTreeMap<GroupKey, Vec<MatchRecord>>,
+}
+
+#[derive(Serialize)]
+struct JsonGroup<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: String,
+    citations: &'a [MatchRecord],
+}
+
+impl ScriptureIndex {
+    pub fn new(
+        group_by: GroupBy,
+        matcher: &BibleMatcher,
+        results: impl Iterator<Item = AnyResult<PathMatches>>,
+    ) -> Self {
+        let mut groups: BTreeMap<GroupKey, Vec<MatchRecord>> = BTreeMap::new();
+        for PathMatches { path, matches } in results.filter_map(Result::ok) {
+            let path = path
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            for m in matches {
+                let Some(record) = MatchRecord::new(matcher, &path, &m) else {
+                    continue;
+                };
+                for key in group_by.keys(matcher, &m) {
+                    groups.entry(key).or_default().push(record.clone());
+                }
+            }
+        }
+
+        for citations in groups.values_mut() {
+            citations.sort_by(|a, b| (&a.path, a.line, a.column).cmp(&(&b.path, b.line, b.column)));
+        }
+
+        Self { group_by, groups }
+    }
+
+    /// Every output mode, with the citations in canonical order of their group
+    pub fn write(&self, matcher: &BibleMatcher, mode: OutputMode) {
+        match mode {
+            OutputMode::Table => self.print_markdown(matcher),
+            OutputMode::JSON => self.print_json(matcher),
+            OutputMode::Count => self.print_count(matcher),
+            OutputMode::Quickfix => {
+                for (_, c) in self.titled(matcher) {
+                    println!("{}", c.quickfix());
+                }
+            }
+            OutputMode::CSV => self.print_rows(matcher, ",", csv_field),
+            OutputMode::TSV => self.print_rows(matcher, "\t", tsv_field),
+        }
+    }
+
+    /// Every citation, after the title of its group (a citation can be in more than one group)
+    fn titled<'a>(
+        &'a self,
+        matcher: &'a BibleMatcher,
+    ) -> impl Iterator<Item = (String, &'a MatchRecord)> + 'a {
+        self.groups.iter().flat_map(move |(key, citations)| {
+            let title = key.title(matcher);
+            citations
+                .iter()
+                .filter_map(move |c| Some((title.clone()?, c)))
+        })
+    }
+
+    /// How many citations each group has
+    pub fn print_count(&self, matcher: &BibleMatcher) {
+        for (key, citations) in self.groups.iter() {
+            if let Some(title) = key.title(matcher) {
+                println!("{}: {}", title, citations.len());
+            }
+        }
+    }
+
+    /// - [`MatchRecord::FIELDS`], with the group they are in first
+    /// - One row per citation in each group
+    fn print_rows(&self, matcher: &BibleMatcher, separator: &str, field: fn(&str) -> String) {
+        let header = std::iter::once("group").chain(MatchRecord::FIELDS);
+        println!("{}", header.collect::<Vec<_>>().join(separator));
+        for (title, c) in self.titled(matcher) {
+            let values = std::iter::once(title).chain(c.values());
+            let row: Vec<String> = values.map(|v| field(&v)).collect();
+            println!("{}", row.join(separator));
+        }
+    }
+
+    /// - Passage groups already name the passage, so each citation is just the location
+    /// - Book and chapter groups also show which passage was cited
+    pub fn print_markdown(&self, matcher: &BibleMatcher) {
+        for (key, citations) in self.groups.iter() {
+            let Some(title) = key.title(matcher) else {
+                continue;
+            };
+            println!("## {}\n", title);
+            for c in citations {
+                match self.group_by {
+                    GroupBy::Passage => println!("- {}:{}:{}", c.path, c.line, c.column),
+                    GroupBy::Book | GroupBy::Chapter => {
+                        println!("- {}:{}:{}: {}", c.path, c.line, c.column, c.passage)
+                    }
+                }
+            }
+            println!();
+        }
+    }
+
+    /// One JSON object per group, in canonical order
+    pub fn print_json(&self, matcher: &BibleMatcher) {
+        for (key, citations) in self.groups.iter() {
+            let Some(title) = key.title(matcher) else {
+                continue;
+            };
+            let group = JsonGroup {
+                kind: "group",
+                title,
+                citations,
+            };
+            println!("{}", serde_json::to_string(&group).unwrap_or_default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use topos_lib::matcher::{location::line_col::LineColLocation, matcher::BibleMatcher};
+
+    use super::GroupBy;
+
+    #[test]
+    fn range_keys() {
+        let matcher = BibleMatcher::default();
+        let matches = matcher
+            .search::<LineColLocation>("Gen 50:26 - Ex 1:7 and Matthew 28 - Acts 1")
+            .unwrap();
+        let titles = |by: GroupBy, i: usize| -> Vec<String> {
+            by.keys(&matcher, &matches[i])
+                .iter()
+                .filter_map(|key| key.title(&matcher))
+                .collect()
+        };
+
+        assert_eq!(titles(GroupBy::Chapter, 0), vec!["Genesis 50", "Exodus 1"]);
+        assert_eq!(
+            titles(GroupBy::Book, 1),
+            vec!["Matthew", "Mark", "Luke", "John", "Acts"]
+        );
+        let chapters = titles(GroupBy::Chapter, 1);
+        assert_eq!(chapters.first().map(String::as_str), Some("Matthew 28"));
+        assert!(chapters.contains(&"Mark 16".to_string()));
+        assert_eq!(chapters.last().map(String::as_str), Some("Acts 1"));
+        // the passage is still where the range starts
+        assert_eq!(titles(GroupBy::Passage, 1), vec!["Matthew 28"]);
+    }
+}
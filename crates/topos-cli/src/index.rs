@@ -0,0 +1,493 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use clap::Subcommand;
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use topos_lib::{
+    data::chapter_verses::VerseOrdinal,
+    error::AnyResult,
+    matcher::{
+        citation::Citation,
+        instance::{BibleMatch, Resolution},
+        location::line_col::{ByteIndex, LineColLocation, Position},
+        matcher::BibleMatcher,
+    },
+    segments::{
+        intervals::IntervalIndex, passage_range::PassageRange, segments::Passage,
+        units::range_pair::RangePair,
+    },
+};
+
+use crate::{matches::PathMatches, outputs::OutputMode};
+
+const DEFAULT_INDEX: &str = ".topos-index.json";
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum IndexCommand {
+    /// Scan a directory and store every match in the index (only changed files are re-scanned)
+    Build {
+        #[clap(help = "The directory to index", default_value = ".")]
+        input: PathBuf,
+        #[clap(long = "index", help = "The index file", default_value = DEFAULT_INDEX)]
+        index: PathBuf,
+    },
+    /// List every indexed citation that overlaps a passage
+    Query {
+        #[clap(help = "The passage to look up (e.g. \"Rom 8:28-30\")")]
+        passage: String,
+        #[clap(long = "index", help = "The index file", default_value = DEFAULT_INDEX)]
+        index: PathBuf,
+        #[clap(
+            long = "mode",
+            short = 'm',
+            help = "Specify output mode",
+            default_value_t
+        )]
+        #[arg(value_enum)]
+        mode: OutputMode,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModifiedTime {
+    pub secs: u64,
+    pub nanos: u32,
+}
+
+impl ModifiedTime {
+    pub fn of(path: &Path) -> std::io::Result<Self> {
+        let modified = fs::metadata(path)?.modified()?;
+        let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Ok(Self {
+            secs: since_epoch.as_secs(),
+            nanos: since_epoch.subsec_nanos(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndexedCitation {
+    pub passage: Passage,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub bytes: (usize, usize),
+    /// A range into a later book, which `passage` is only the start of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<PassageRange>,
+    /// One inclusive range per segment (of every book, for a range across books)
+    pub ordinals: Vec<RangePair<VerseOrdinal>>,
+    // what the match knew about itself (index files from before these were kept read as the
+    // defaults of an explicit reference)
+    #[serde(default)]
+    pub resolution: Resolution,
+    #[serde(default = "full_confidence")]
+    pub confidence: f32,
+    #[serde(default = "always_valid")]
+    pub valid: bool,
+    #[serde(default)]
+    pub citation: Citation,
+}
+
+fn full_confidence() -> f32 {
+    1.0
+}
+
+fn always_valid() -> bool {
+    true
+}
+
+impl IndexedCitation {
+    pub fn new(matcher: &BibleMatcher, m: BibleMatch) -> Self {
+        let location = m.location;
+        let chapter_verses = matcher.data().chapter_verses();
+        let ordinals = match &m.range {
+            Some(range) => range
+                .passages(chapter_verses)
+                .iter()
+                .flat_map(|psg| chapter_verses.passage_ordinals(psg))
+                .collect(),
+            None => chapter_verses.passage_ordinals(&m.psg),
+        };
+        Self {
+            ordinals,
+            range: m.range,
+            passage: m.psg,
+            start: (location.start.line, location.start.column),
+            end: (location.end.line, location.end.column),
+            bytes: (location.bytes.start, location.bytes.end),
+            resolution: m.resolution,
+            confidence: m.confidence,
+            valid: m.valid,
+            citation: m.citation,
+        }
+    }
+
+    pub fn to_match(&self) -> BibleMatch {
+        let location = LineColLocation {
+            start: Position::new_pair(self.start),
+            end: Position::new_pair(self.end),
+            bytes: ByteIndex::new(self.bytes.0, self.bytes.1),
+        };
+        let m = BibleMatch::new(location, self.passage.book, self.passage.segments.clone())
+            .with_resolution(self.resolution.clone())
+            .with_confidence(self.confidence)
+            .with_valid(self.valid)
+            .with_citation(self.citation.clone());
+        match &self.range {
+            Some(range) => m.with_range(range.clone()),
+            None => m,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndexedFile {
+    pub modified: ModifiedTime,
+    pub citations: Vec<IndexedCitation>,
+}
+
+/// Where a citation is in the index: the file (in path order), and the citation in that file
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct CitationId {
+    file: usize,
+    citation: usize,
+}
+
+/**
+- Every match in a directory, keyed by file (and the file's modified time)
+- Rebuilding only re-scans files whose modified time changed, and drops files that are gone
+- Filters and options given to `index build` decide what gets stored, so when they change (see
+[`Args::fingerprint`](crate::args::Args::fingerprint)) every file is scanned again
+*/
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ReferenceIndex {
+    /// The filters and options the index was built with
+    #[serde(default)]
+    pub fingerprint: String,
+    pub files: BTreeMap<PathBuf, IndexedFile>,
+    /// The paths of `files`, in order, which [`CitationId::file`] points into
+    #[serde(skip)]
+    paths: Vec<PathBuf>,
+    /// Built once (on load or update), and then only queried
+    #[serde(skip)]
+    intervals: IntervalIndex<CitationId>,
+}
+
+impl ReferenceIndex {
+    pub fn load(path: &Path) -> AnyResult<Self> {
+        let text = fs::read_to_string(path)?;
+        let mut index: Self = serde_json::from_str(&text)?;
+        index.build_intervals();
+        Ok(index)
+    }
+
+    pub fn save(&self, path: &Path) -> AnyResult<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /**
+    - Returns how many files had to be (re-)scanned
+    - Everything is scanned again when `fingerprint` isn't the one the index was built with
+    - Files that can't be read (or stat'ed) are logged and skipped, like walk errors
+    */
+    pub fn update(
+        &mut self,
+        matcher: &BibleMatcher,
+        dir: &Path,
+        fingerprint: &str,
+    ) -> AnyResult<usize> {
+        if self.fingerprint != fingerprint {
+            self.files.clear();
+            self.fingerprint = fingerprint.to_string();
+        }
+
+        let mut files = BTreeMap::new();
+        let mut scanned = 0;
+        for entry in WalkBuilder::new(dir).build() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    eprintln!("Error: {}", err);
+                    continue;
+                }
+            };
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let path = entry.into_path();
+            let modified = match ModifiedTime::of(&path) {
+                Ok(modified) => modified,
+                Err(err) => {
+                    eprintln!("Error: {:?}: {}", path, err);
+                    continue;
+                }
+            };
+
+            if let Some(file) = self.files.remove(&path) {
+                if file.modified == modified {
+                    files.insert(path, file);
+                    continue;
+                }
+            }
+
+            scanned += 1;
+            // non-text files can't be read, so there's nothing to index (until they change)
+            let citations = match PathMatches::from_file(path.clone(), matcher) {
+                Ok(PathMatches { matches, .. }) => matches
+                    .into_iter()
+                    .map(|m| IndexedCitation::new(matcher, m))
+                    .collect(),
+                Err(_) => vec![],
+            };
+            files.insert(
+                path,
+                IndexedFile {
+                    modified,
+                    citations,
+                },
+            );
+        }
+        self.files = files;
+        self.build_intervals();
+        Ok(scanned)
+    }
+
+    fn build_intervals(&mut self) {
+        self.paths = self.files.keys().cloned().collect();
+        self.intervals = self
+            .files
+            .values()
+            .enumerate()
+            .flat_map(|(file, indexed)| {
+                indexed
+                    .citations
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(citation, c)| {
+                        let id = CitationId { file, citation };
+                        c.ordinals.iter().map(move |range| (*range, id))
+                    })
+            })
+            .collect();
+    }
+
+    /// Every citation overlapping the passage, grouped by file (in path order)
+    pub fn query(&self, matcher: &BibleMatcher, psg: &Passage) -> Vec<PathMatches> {
+        // a citation with several segments can overlap more than once
+        let mut found: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+        for range in matcher.data().chapter_verses().passage_ordinals(psg) {
+            for id in self.intervals.overlapping(range) {
+                found.entry(id.file).or_default().insert(id.citation);
+            }
+        }
+
+        found
+            .into_iter()
+            .map(|(file, citations)| {
+                let path = &self.paths[file];
+                let indexed = &self.files[path];
+                // citations are stored in the order they were found in the file
+                let matches = citations
+                    .into_iter()
+                    .map(|idx| indexed.citations[idx].to_match())
+                    .collect();
+                PathMatches::new(matches).with_path(path.clone())
+            })
+            .collect()
+    }
+}
+
+impl IndexCommand {
+    /// `fingerprint` is what the matcher was built from, see [`Args::fingerprint`](crate::args::Args::fingerprint)
+    pub fn run(self, matcher: &BibleMatcher, fingerprint: &str) -> AnyResult<()> {
+        match self {
+            IndexCommand::Build { input, index } => {
+                let mut reference_index = if index.exists() {
+                    ReferenceIndex::load(&index)?
+                } else {
+                    ReferenceIndex::default()
+                };
+                let scanned = reference_index.update(matcher, &input, fingerprint)?;
+                reference_index.save(&index)?;
+                eprintln!(
+                    "Indexed {} files ({} scanned) into {:?}",
+                    reference_index.files.len(),
+                    scanned,
+                    index
+                );
+            }
+            IndexCommand::Query {
+                passage,
+                index,
+                mode,
+            } => {
+                let psg = matcher
+                    .data()
+                    .parse(&passage)
+                    .ok_or_else(|| format!("Could not parse passage {:?}", passage))?;
+                let reference_index = ReferenceIndex::load(&index)?;
+                let results = reference_index.query(matcher, &psg);
+                mode.write(matcher, results.into_iter().map(Ok));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime},
+    };
+
+    use topos_lib::matcher::{
+        citation::{Placement, Role},
+        instance::Resolution,
+        matcher::BibleMatcher,
+    };
+
+    use super::{IndexedCitation, ReferenceIndex};
+
+    /// An empty directory of its own for each test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("topos-index-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Writes `text`, and moves its modified time forward, so it changes even on coarse clocks
+    fn write(path: &Path, text: &str, later: u64) {
+        fs::write(path, text).unwrap();
+        let modified = SystemTime::now() + Duration::from_secs(later);
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn update() {
+        let dir = temp_dir("update");
+        let matcher = BibleMatcher::default();
+        write(&dir.join("a.md"), "Romans 8:28", 0);
+        write(&dir.join("b.md"), "John 3:16", 0);
+
+        let mut index = ReferenceIndex::default();
+        assert_eq!(index.update(&matcher, &dir, "").unwrap(), 2);
+
+        // unchanged files are not scanned again
+        assert_eq!(index.update(&matcher, &dir, "").unwrap(), 0);
+
+        // but modified ones are
+        write(&dir.join("b.md"), "John 3:16 and John 3:17", 10);
+        assert_eq!(index.update(&matcher, &dir, "").unwrap(), 1);
+        assert_eq!(index.files[&dir.join("b.md")].citations.len(), 2);
+
+        // and deleted ones are dropped
+        fs::remove_file(dir.join("a.md")).unwrap();
+        assert_eq!(index.update(&matcher, &dir, "").unwrap(), 0);
+        assert_eq!(
+            index.files.keys().collect::<Vec<_>>(),
+            vec![&dir.join("b.md")]
+        );
+
+        // different options scan everything again
+        assert_eq!(index.update(&matcher, &dir, "--anaphora").unwrap(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn query() {
+        let dir = temp_dir("query");
+        let matcher = BibleMatcher::default();
+        write(
+            &dir.join("b.md"),
+            "Romans 8:28-30, Romans 8:31, and Romans 8",
+            0,
+        );
+        write(&dir.join("a.md"), "John 3:16, Romans 8:29", 0);
+        write(&dir.join("c.md"), "Genesis 1:1", 0);
+
+        let mut index = ReferenceIndex::default();
+        index.update(&matcher, &dir, "").unwrap();
+        // what a later `index query` would load
+        let index_path = dir.join("index.json");
+        index.save(&index_path).unwrap();
+        let index = ReferenceIndex::load(&index_path).unwrap();
+
+        let psg = matcher.data().parse("Rom 8:29-30").unwrap();
+        let found: Vec<(PathBuf, Vec<String>)> = index
+            .query(&matcher, &psg)
+            .into_iter()
+            .map(|pm| {
+                let passages = pm
+                    .matches
+                    .iter()
+                    .map(|m| matcher.data().format_passage(&m.psg).unwrap())
+                    .collect();
+                (pm.path.unwrap(), passages)
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (dir.join("a.md"), vec!["Romans 8:29".to_string()]),
+                (
+                    dir.join("b.md"),
+                    vec!["Romans 8:28-30".to_string(), "Romans 8".to_string()]
+                ),
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn match_details() {
+        let dir = temp_dir("details");
+        let matcher = BibleMatcher::default();
+        write(
+            &dir.join("a.md"),
+            "Romans 8:28 is clear (cf. John 3:16).",
+            0,
+        );
+
+        let mut index = ReferenceIndex::default();
+        index.update(&matcher, &dir, "").unwrap();
+        let index_path = dir.join("index.json");
+        index.save(&index_path).unwrap();
+        let index = ReferenceIndex::load(&index_path).unwrap();
+
+        // a hit is what the match was, not a new explicit one
+        let psg = matcher.data().parse("John 3").unwrap();
+        let found = index.query(&matcher, &psg);
+        let m = &found[0].matches[0];
+        assert_eq!(m.citation.role, Role::CrossReference);
+        assert_eq!(m.citation.marker.as_deref(), Some("cf."));
+        assert_eq!(m.citation.placement, Placement::Parenthetical);
+
+        // index files from before these were kept still load
+        let citation = &index.files[&dir.join("a.md")].citations[0];
+        let mut json = serde_json::to_value(citation).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        for key in ["resolution", "confidence", "valid", "citation"] {
+            fields.remove(key);
+        }
+        let old: IndexedCitation = serde_json::from_value(json).unwrap();
+        let m = old.to_match();
+        assert_eq!(m.resolution, Resolution::Explicit);
+        assert_eq!(m.confidence, 1.0);
+        assert!(m.valid);
+        assert_eq!(m.citation.role, Role::Primary);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,20 +1,49 @@
 use clap::Parser;
 use topos_lib::matcher::matcher::BibleMatcher;
 
-use crate::{args::Args, inputs::InputType};
+use crate::{args::Args, commands::Command, groups::ScriptureIndex, inputs::InputType};
 
 pub mod args;
+pub mod commands;
+pub mod digest;
+#[cfg(feature = "sqlite")]
+pub mod export;
+pub mod groups;
+pub mod index;
 pub mod inputs;
 pub mod matches;
 pub mod outputs;
 
 pub fn main() {
     let args = Args::parse();
-    let input = InputType::new(args.input.clone());
+    let command = args.command.clone();
+    let input = args.input.clone();
     let output = args.mode;
+    let group_by = args.group_by;
+    let fingerprint = args.fingerprint();
 
     let matcher = BibleMatcher::try_from(args).unwrap();
 
+    if let Some(command) = command {
+        let result = match command {
+            Command::Digest(digest) => digest.run(&matcher),
+            Command::Index(index) => index.run(&matcher, &fingerprint),
+            #[cfg(feature = "sqlite")]
+            Command::Export(export) => export.run(&matcher),
+        };
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let input = InputType::new(input);
     let results = input.search(matcher.clone());
-    output.write(&matcher, results);
+    match group_by {
+        Some(group_by) => {
+            ScriptureIndex::new(group_by, &matcher, results).write(&matcher, output);
+        }
+        None => output.write(&matcher, results),
+    }
 }
@@ -1,7 +1,14 @@
 use std::time::Instant;
 
 use clap::ValueEnum;
-use topos_lib::{error::AnyResult, matcher::matcher::BibleMatcher, segments::segments::Passage};
+use serde::Serialize;
+use topos_lib::{
+    error::AnyResult,
+    matcher::{instance::BibleMatch, matcher::BibleMatcher},
+    segments::segments::Passage,
+};
+
+use unicode_width::UnicodeWidthStr;
 
 use crate::matches::PathMatches;
 
@@ -16,20 +23,203 @@ pub enum OutputMode {
     Table,
     #[value(alias = "qf", help = "Output matches for the Neovim Quickfix List")]
     Quickfix,
+    #[value(help = "Output matches as comma-separated values")]
+    CSV,
+    #[value(help = "Output matches as tab-separated values")]
+    TSV,
+}
+
+/**
+- A single match, flattened into the fields that every output mode shares
+- JSON, CSV, and TSV all write exactly these fields (in this order), so they stay interchangeable
+*/
+#[derive(Clone, Debug, Serialize)]
+pub struct MatchRecord {
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub book: String,
+    pub segments: String,
+    pub passage: String,
+    /// The passage (or range) as an OSIS reference (`John.3.16-John.3.18`)
+    pub osis: String,
+    /// `explicit`, or how the book was inferred
+    pub resolution: String,
+    /// How likely this is to really be a reference, from `0.0` to `1.0`
+    pub confidence: f32,
+    /// Whether every chapter and verse of it exists
+    pub valid: bool,
+    /// `primary`, or what the marker before it says it's cited for (`cross-reference` for `cf.`)
+    pub role: String,
+    /// The marker before it, as it was written (`cf.`, `see also`)
+    pub marker: String,
+    /// `inline`, `parenthetical`, or `footnote`
+    pub placement: String,
+}
+
+impl MatchRecord {
+    pub const FIELDS: [&'static str; 17] = [
+        "path",
+        "line",
+        "column",
+        "end_line",
+        "end_column",
+        "start_byte",
+        "end_byte",
+        "book",
+        "segments",
+        "passage",
+        "osis",
+        "resolution",
+        "confidence",
+        "valid",
+        "role",
+        "marker",
+        "placement",
+    ];
+
+    pub fn new(matcher: &BibleMatcher, path: &str, m: &BibleMatch) -> Option<Self> {
+        let Passage { book, segments } = &m.psg;
+        let book = matcher.data().books().get_name(*book)?.to_string();
+        let segments = segments.to_string();
+        // a range across books is the whole range, but `book` and `segments` are only its first book
+        let (passage, osis) = match &m.range {
+            Some(range) => (
+                matcher.data().format_range(range)?,
+                matcher.data().format_osis_range(range),
+            ),
+            None => (
+                matcher.data().format_passage(&m.psg)?,
+                matcher.data().format_osis(&m.psg),
+            ),
+        };
+        let location = &m.location;
+        Some(Self {
+            path: path.to_string(),
+            line: location.start.line,
+            column: location.start.column,
+            end_line: location.end.line,
+            end_column: location.end.column,
+            start_byte: location.bytes.start,
+            end_byte: location.bytes.end,
+            book,
+            segments,
+            passage,
+            // custom book data might not have OSIS codes
+            osis: osis.unwrap_or_default(),
+            resolution: m.resolution.to_string(),
+            confidence: m.confidence,
+            valid: m.valid,
+            role: m.citation.role.to_string(),
+            marker: m.citation.marker.clone().unwrap_or_default(),
+            placement: m.citation.placement.to_string(),
+        })
+    }
+
+    /// The values of [`MatchRecord::FIELDS`], in the same order
+    pub fn values(&self) -> [String; 17] {
+        [
+            self.path.clone(),
+            self.line.to_string(),
+            self.column.to_string(),
+            self.end_line.to_string(),
+            self.end_column.to_string(),
+            self.start_byte.to_string(),
+            self.end_byte.to_string(),
+            self.book.clone(),
+            self.segments.clone(),
+            self.passage.clone(),
+            self.osis.clone(),
+            self.resolution.clone(),
+            format!("{:.2}", self.confidence),
+            self.valid.to_string(),
+            self.role.clone(),
+            self.marker.clone(),
+            self.placement.clone(),
+        ]
+    }
+}
+
+/// Column headers are static, but each entry formats itself
+pub trait OutputEntryFormat {
+    fn json(&self) -> String;
+    fn csv(&self) -> String;
+    fn tsv(&self) -> String;
+    fn quickfix(&self) -> String;
+}
+
+#[derive(Serialize)]
+struct JsonEntry<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(flatten)]
+    record: &'a MatchRecord,
+}
+
+impl OutputEntryFormat for MatchRecord {
+    fn json(&self) -> String {
+        let entry = JsonEntry {
+            kind: "match",
+            record: self,
+        };
+        serde_json::to_string(&entry).unwrap_or_default()
+    }
+
+    fn csv(&self) -> String {
+        self.values()
+            .iter()
+            .map(|v| csv_field(v))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn tsv(&self) -> String {
+        self.values()
+            .iter()
+            .map(|v| tsv_field(v))
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+
+    fn quickfix(&self) -> String {
+        format!(
+            "{}:{}:{}: {}",
+            self.path, self.line, self.column, self.passage
+        )
+    }
+}
+
+/// - Quote a field only when it needs it (RFC 4180): commas, quotes, or line breaks
+/// - Inner quotes are doubled
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// TSV has no quoting, so tabs and line breaks are escaped the way most spreadsheet tools expect
+pub fn tsv_field(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
 }
 
-/// But what about static methods, for things like column headers
-trait OutputEntryFormat {
-    fn json(self) -> String;
-    fn table(self) -> String;
-    fn quickfix(self) -> String;
+/// A markdown table cell, where a `|` would start the next cell
+fn table_cell(value: &str) -> String {
+    value.replace('|', "\\|")
 }
 
 impl OutputMode {
     /**
-    TODO: this should not return an iterator of a struct, but an iterator of a type
-    This type should implement [`OutputEntryFormat`]
-    This is so that I can do different kinds of JSON outputs for example, based on the verbosity that the user requests (like context, ..)
+    TODO: I still want different kinds of JSON outputs, based on the verbosity that the user requests (like context, ..)
     */
     pub fn write(
         &self,
@@ -40,22 +230,39 @@ impl OutputMode {
             OutputMode::Count => print_time(matcher, results),
             OutputMode::JSON => print_json(matcher, results),
             OutputMode::Table => print_table(matcher, results),
-            OutputMode::Quickfix => print_qf_list(matcher, results),
+            OutputMode::Quickfix => print_entries(matcher, results, MatchRecord::quickfix),
+            OutputMode::CSV => {
+                println!("{}", MatchRecord::FIELDS.join(","));
+                print_entries(matcher, results, MatchRecord::csv)
+            }
+            OutputMode::TSV => {
+                println!("{}", MatchRecord::FIELDS.join("\t"));
+                print_entries(matcher, results, MatchRecord::tsv)
+            }
         }
     }
 }
 
+fn records(
+    matcher: &BibleMatcher,
+    PathMatches { path, matches }: PathMatches,
+) -> impl Iterator<Item = MatchRecord> {
+    let path = path
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    matches
+        .into_iter()
+        .filter_map(move |m| MatchRecord::new(matcher, &path, &m))
+}
+
 /// BUG: The problem is that I am running the timer at the wrong spot, I think once the iterator is
 /// created, it means all items have been sent
 /// Here's an idea: pass the OutputMode to the matcher and call it on each iteration
-fn print_time(matcher: &BibleMatcher, results: impl Iterator<Item = AnyResult<PathMatches>>) {
+fn print_time(_matcher: &BibleMatcher, results: impl Iterator<Item = AnyResult<PathMatches>>) {
     let start = Instant::now();
     let mut count = 0;
     // i think it skips this completely because there is code after it
-    for PathMatches { path, matches } in results.filter_map(Result::ok) {
-        let path = path
-            .map(|p| p.to_string_lossy().into_owned())
-            .unwrap_or_default();
+    for PathMatches { matches, .. } in results.filter_map(Result::ok) {
         count += matches.len();
     }
     println!("Matches: {}", count);
@@ -63,71 +270,101 @@ fn print_time(matcher: &BibleMatcher, results: impl Iterator<Item = AnyResult<Pa
 }
 
 fn print_json(matcher: &BibleMatcher, results: impl Iterator<Item = AnyResult<PathMatches>>) {
-    for PathMatches { path, matches } in results.filter_map(Result::ok) {
-        let path = path
+    for path_matches in results.filter_map(Result::ok) {
+        let path = path_matches
+            .path
+            .as_ref()
             .map(|p| p.to_string_lossy().into_owned())
             .unwrap_or_default();
-        println!(r#"{{ "type": "start", "path": "{}" }}"#, path);
-        for m in matches {
-            let Passage { book, segments } = m.psg;
-
-            let Some(book) = matcher.data().books().get_name(book) else {
-                continue;
-            };
-
-            let start = m.location.start;
-            let psg = format!("{} {}", book, segments);
-
-            // println!("{}:{}:{}: {}", path, start.line, start.column, psg)
-            println!(r#"{{ "type": "match" }}"#);
+        let path = serde_json::to_string(&path).unwrap_or_default();
+        println!(r#"{{ "type": "start", "path": {} }}"#, path);
+        for record in records(matcher, path_matches) {
+            println!("{}", record.json());
         }
-        println!(r#"{{ "type": "end", "path": "{}" }}"#, path);
+        println!(r#"{{ "type": "end", "path": {} }}"#, path);
     }
 }
 
-fn print_qf_list<'a>(
+/// One line per match, no headers or grouping
+fn print_entries(
     matcher: &BibleMatcher,
     results: impl Iterator<Item = AnyResult<PathMatches>>,
+    format: fn(&MatchRecord) -> String,
 ) {
-    for PathMatches { path, matches } in results.filter_map(Result::ok) {
-        let path = path
-            .map(|p| p.to_string_lossy().into_owned())
-            .unwrap_or_default();
-        for m in matches {
-            let Passage { book, segments } = m.psg;
+    for path_matches in results.filter_map(Result::ok) {
+        for record in records(matcher, path_matches) {
+            println!("{}", format(&record));
+        }
+    }
+}
 
-            let Some(book) = matcher.data().books().get_name(book) else {
-                continue;
-            };
+/**
+- Every row has to be known before the column widths are, so this collects all matches first
+- Widths are counted in terminal columns, not bytes or characters, so non-ASCII paths and book
+names still line up (each character of `马太福音` is two columns wide)
+*/
+fn print_table(matcher: &BibleMatcher, results: impl Iterator<Item = AnyResult<PathMatches>>) {
+    const HEADERS: [&str; 4] = ["File", "Line", "Col", "Verse"];
 
-            let start = m.location.start;
-            let psg = format!("{} {}", book, segments);
+    let rows: Vec<[String; 4]> = results
+        .filter_map(Result::ok)
+        .flat_map(|path_matches| records(matcher, path_matches))
+        .map(|r| {
+            [r.path, r.line.to_string(), r.column.to_string(), r.passage].map(|c| table_cell(&c))
+        })
+        .collect();
 
-            println!("{}:{}:{}: {}", path, start.line, start.column, psg)
+    let mut widths = HEADERS.map(|h| h.width());
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
         }
     }
+
+    let format_row = |cells: [&str; 4]| {
+        let cells = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.width())))
+            .collect::<Vec<_>>();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    println!("{}", format_row(HEADERS));
+    let dashes = widths.map(|w| "-".repeat(w));
+    println!("{}", format_row(dashes.each_ref().map(String::as_str)));
+    for row in rows.iter() {
+        println!("{}", format_row(row.each_ref().map(String::as_str)));
+    }
 }
 
-fn print_table<'a>(matcher: &BibleMatcher, results: impl Iterator<Item = AnyResult<PathMatches>>) {
-    println!("| File | Line | Col | Verse |");
-    println!("| ---- | ---- | --- | ----- |");
-    for PathMatches { path, matches } in results.filter_map(Result::ok) {
-        let path = path
-            .map(|p| p.to_string_lossy().into_owned())
-            .unwrap_or_default();
-        for m in matches {
-            let Passage { book, segments } = m.psg;
+#[cfg(test)]
+mod tests {
+    use super::{csv_field, table_cell, tsv_field};
 
-            let Some(book) = matcher.data().books().get_name(book) else {
-                continue;
-            };
+    #[test]
+    fn csv_fields() {
+        assert_eq!(csv_field("Romans 8:28"), "Romans 8:28");
+        assert_eq!(csv_field("Romans 8:28,30"), "\"Romans 8:28,30\"");
+        assert_eq!(csv_field("a \"quote\""), "\"a \"\"quote\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        // the rest aren't special in CSV
+        assert_eq!(csv_field("a\tb"), "a\tb");
+        assert_eq!(csv_field("C:\\notes"), "C:\\notes");
+        assert_eq!(csv_field("a|b"), "a|b");
+    }
 
-            let start = m.location.start;
+    #[test]
+    fn tsv_fields() {
+        assert_eq!(tsv_field("a\tb"), "a\\tb");
+        assert_eq!(tsv_field("two\nlines"), "two\\nlines");
+        assert_eq!(tsv_field("C:\\notes"), "C:\\\\notes");
+        assert_eq!(tsv_field("a,b \"c\" d|e"), "a,b \"c\" d|e");
+    }
 
-            println!(
-                "| {} | {} | {} | {} {} |",
-                path, start.line, start.column, book, segments
-            )
-        }
+    #[test]
+    fn table_cells() {
+        assert_eq!(table_cell("a|b"), "a\\|b");
+        assert_eq!(table_cell("Romans 8:28, \"c\""), "Romans 8:28, \"c\"");
     }
 }
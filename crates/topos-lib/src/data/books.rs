@@ -5,7 +5,15 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::segments::segments::{Passage, Segments};
+use crate::{
+    data::{detector::BookDetector, locale::Locale},
+    segments::{
+        parser::{components::Delimeter, minimal::MinimalSegments},
+        passage_range::PassageRange,
+        segment::Segment,
+        segments::{Passage, Segments},
+    },
+};
 
 /// This is not guaranteed to be a valid key, I just am using a unique type
 #[derive(
@@ -31,17 +39,31 @@ pub struct BookId(pub u8);
 //     abbrev: String,
 // }
 
-/// eventually this will have a locale so i can group by languages
+/**
+- The book names of one or more [`Locale`]s
+- Every locale's names and abbreviations are searched, but only the first locale's names are
+displayed (see [`Books::with_display_locale`] to display another)
+*/
 #[derive(Clone, Debug)]
 pub struct Books {
     /// map of abbreviations and actual name (all lowercase) to book id (for searching)
     input_to_book_id: BTreeMap<String, BookId>,
+    /// map of every locale's book name (case kept) to book id (for whole-book references)
+    name_to_book_id: BTreeMap<String, BookId>,
     /// map of book id to book name (for display)
     book_id_to_name: BTreeMap<BookId, String>,
     /// map of book id to abbreviation (for display)
     book_id_to_abbreviation: BTreeMap<BookId, String>,
-    /// match any book; used to parse complex filters
-    passage_regex: Regex,
+    /// map of book id to the testament it is in (for filtering and canons)
+    book_id_to_testament: BTreeMap<BookId, Testament>,
+    /// map of book id to OSIS code (`1Cor`), the same in every locale
+    book_id_to_osis: BTreeMap<BookId, String>,
+    /// map of OSIS code (all lowercase) to book id
+    osis_to_book_id: BTreeMap<String, BookId>,
+    /// finds any book; used to parse complex filters, and shared by everything that needs every book
+    detector: BookDetector,
+    /// finds only the full names (of every locale), for whole-book references
+    name_detector: BookDetector,
 }
 
 impl Books {
@@ -60,16 +82,45 @@ impl Books {
     pub fn iter_keys_and_ids(&self) -> impl Iterator<Item = (&String, &BookId)> {
         self.key_to_id().iter()
     }
+    pub fn iter_ids_and_names(&self) -> impl Iterator<Item = (&BookId, &String)> {
+        self.id_to_name().iter()
+    }
+    /// The full name of every book in every locale that was loaded, not just the displayed ones
+    pub fn iter_names_and_ids(&self) -> impl Iterator<Item = (&String, &BookId)> {
+        self.name_to_book_id.iter()
+    }
     pub fn search(&self, name: &str) -> Option<BookId> {
         let name = Self::normalize_book_name(name);
         self.key_to_id().get(&name).cloned()
     }
+    /// The book with this full name, written with the same capitalization as the book data
+    pub fn search_name(&self, name: &str) -> Option<BookId> {
+        self.name_to_book_id.get(name).copied()
+    }
     pub fn get_name(&self, id: BookId) -> Option<&String> {
         self.id_to_name().get(&id)
     }
     pub fn get_abbrev(&self, id: BookId) -> Option<&String> {
         self.id_to_abbrev().get(&id)
     }
+    pub fn get_testament(&self, id: BookId) -> Option<Testament> {
+        self.book_id_to_testament.get(&id).copied()
+    }
+    /// The OSIS code of a book (`Gen`, `1Cor`, `Ps`)
+    pub fn get_osis(&self, id: BookId) -> Option<&String> {
+        self.book_id_to_osis.get(&id)
+    }
+    /// The book with an OSIS code, ignoring case (`1cor` is `1Cor`)
+    pub fn search_osis(&self, code: &str) -> Option<BookId> {
+        self.osis_to_book_id.get(&code.to_lowercase()).copied()
+    }
+    /// Every book in `testament`, in canonical order
+    pub fn ids_in(&self, testament: Testament) -> impl Iterator<Item = BookId> + '_ {
+        self.book_id_to_testament
+            .iter()
+            .filter(move |(_, t)| **t == testament)
+            .map(|(id, _)| *id)
+    }
 }
 
 impl Default for Books {
@@ -83,43 +134,233 @@ impl Books {
     /// - If you would like English book names, please just use [`Default::default()`]
     pub fn new(data: BooksInput) -> Result<Self, String> {
         let mut abbreviations_to_book_id = BTreeMap::new();
+        let mut name_to_book_id = BTreeMap::new();
         let mut book_id_to_name = BTreeMap::new();
         let mut book_id_to_abbreviation = BTreeMap::new();
+        let mut book_id_to_testament = BTreeMap::new();
+        let mut book_id_to_osis = BTreeMap::new();
 
+        let mut generated = vec![];
         for book in data.0 {
-            abbreviations_to_book_id.insert(Books::normalize_book_name(&book.book), book.id);
+            let keys = std::iter::once(&book.book).chain(&book.abbreviations);
+            for key in keys {
+                let key = Books::normalize_book_name(key);
+                generated.extend(numbered_variants(&key).into_iter().map(|v| (v, book.id)));
+                abbreviations_to_book_id.insert(key, book.id);
+            }
+            name_to_book_id.insert(book.book.clone(), book.id);
             book_id_to_name.insert(book.id, book.book);
-            book_id_to_abbreviation.insert(book.id, book.abbreviation);
-            for abbreviation in book.abbreviations {
-                abbreviations_to_book_id.insert(Books::normalize_book_name(&abbreviation), book.id);
+            if let Some(testament) = book.testament.or_else(|| Testament::from_id(book.id)) {
+                book_id_to_testament.insert(book.id, testament);
             }
+            if let Some(osis) = book.osis.or_else(|| DEFAULT_OSIS.get(&book.id).cloned()) {
+                book_id_to_osis.insert(book.id, osis);
+            }
+            book_id_to_abbreviation.insert(book.id, book.abbreviation);
+        }
+        // the keys that were written out always win over the generated ones
+        for (variant, id) in generated {
+            abbreviations_to_book_id.entry(variant).or_insert(id);
         }
 
-        let books_pattern: String = abbreviations_to_book_id.keys().join("|");
+        Self::compile(
+            abbreviations_to_book_id,
+            name_to_book_id,
+            book_id_to_name,
+            book_id_to_abbreviation,
+            book_id_to_testament,
+            book_id_to_osis,
+        )
+    }
 
-        let passage_regex = Regex::new(format!(r"\b((?:)(?i){books_pattern})\b\.?(.*)").as_str())
-            .map_err(|e| {
-            format!("Failed to compile book_regex because of bad user input.\n{e}")
-        })?;
+    fn compile(
+        input_to_book_id: BTreeMap<String, BookId>,
+        name_to_book_id: BTreeMap<String, BookId>,
+        book_id_to_name: BTreeMap<BookId, String>,
+        book_id_to_abbreviation: BTreeMap<BookId, String>,
+        book_id_to_testament: BTreeMap<BookId, Testament>,
+        book_id_to_osis: BTreeMap<BookId, String>,
+    ) -> Result<Self, String> {
+        let detector = BookDetector::new(&input_to_book_id)?;
+        let mut names = BTreeMap::new();
+        for (name, id) in name_to_book_id.iter() {
+            names.entry(Books::normalize_book_name(name)).or_insert(*id);
+        }
+        let name_detector = BookDetector::new(&names)?;
+        let osis_to_book_id = book_id_to_osis
+            .iter()
+            .map(|(id, osis)| (osis.to_lowercase(), *id))
+            .collect();
 
         Ok(Books {
-            // book_regex,
-            input_to_book_id: abbreviations_to_book_id,
+            input_to_book_id,
+            name_to_book_id,
             book_id_to_name,
             book_id_to_abbreviation,
-            passage_regex,
+            book_id_to_testament,
+            book_id_to_osis,
+            osis_to_book_id,
+            detector,
+            name_detector,
         })
     }
 
-    pub fn parse(&self, input: &str) -> Option<Passage> {
-        let m = &self.passage_regex.captures_iter(input).next()?;
-        let book = m.get(1)?.as_str();
-        let book = self.search(book)?;
-        let segments = m.get(2)?.as_str();
-        let segments = Segments::parse(segments)?;
+    /**
+    - The book names of every locale, searched all at once (`Juan 3:16` and `John 3:16`)
+    - When two locales use the same abbreviation for different books (`Jn` is John in Spanish,
+    but Jonah in Portuguese), the locale listed first wins
+    - Names are displayed in the first locale
+    */
+    pub fn from_locales(locales: &[Locale]) -> Result<Self, String> {
+        let mut locales = locales.iter();
+        let first = locales.next().copied().unwrap_or_default();
+        let mut books = Books::new(BooksInput::from_locale(first)?)?;
+        for locale in locales {
+            let other = Books::new(BooksInput::from_locale(*locale)?)?;
+            for (key, id) in other.input_to_book_id {
+                books.input_to_book_id.entry(key).or_insert(id);
+            }
+            for (name, id) in other.name_to_book_id {
+                books.name_to_book_id.entry(name).or_insert(id);
+            }
+        }
+        Books::compile(
+            books.input_to_book_id,
+            books.name_to_book_id,
+            books.book_id_to_name,
+            books.book_id_to_abbreviation,
+            books.book_id_to_testament,
+            books.book_id_to_osis,
+        )
+    }
+
+    /**
+    - Displays the names and abbreviations of `locale` instead, but keeps searching the same names
+    - Ex: match English and Spanish notes, but output everything in Spanish
+    */
+    pub fn with_display_locale(mut self, locale: Locale) -> Result<Self, String> {
+        let display = Books::new(BooksInput::from_locale(locale)?)?;
+        self.book_id_to_name.extend(display.book_id_to_name);
+        self.book_id_to_abbreviation
+            .extend(display.book_id_to_abbreviation);
+        Ok(self)
+    }
+
+    /// Finds every book name (of every locale that was loaded), see [`BookDetector`]
+    pub fn detector(&self) -> &BookDetector {
+        &self.detector
+    }
+
+    /**
+    - Finds only the full names of books (`Ruth`, `Song of Solomon`), of every locale
+    - Abbreviations are left out, since most of them are regular words without a chapter after them
+    - Like every [`BookDetector`], this ignores case, see [`Books::search_name`]
+    */
+    pub fn name_detector(&self) -> &BookDetector {
+        &self.name_detector
+    }
+
+    /**
+    - The regex alternation of `keys`, case insensitive and escaped
+    - This is much slower than a [`BookDetector`] over many keys, but it can go inside of other
+    patterns (and `rg`)
+    - `\b` doesn't work for scripts that don't put spaces between words (`在约翰福音3:16中`), so
+    Chinese and Japanese keys are matched anywhere
+    - `trailing_boundary` also requires a word boundary after the other keys
+    - This has no capture groups, so it can go inside of other patterns
+    */
+    pub fn keys_pattern<'a>(
+        keys: impl IntoIterator<Item = &'a String>,
+        trailing_boundary: bool,
+    ) -> String {
+        let (unspaced, spaced): (Vec<&String>, Vec<&String>) = keys
+            .into_iter()
+            .partition(|key| UNSPACED_SCRIPT.is_match(key));
+        let spaced = spaced.into_iter().map(|key| regex::escape(key)).join("|");
+        // nothing after them stops a shorter key from winning (`约` in `约翰福音`)
+        let unspaced = unspaced
+            .into_iter()
+            .sorted_by_key(|key| std::cmp::Reverse(key.len()))
+            .map(|key| regex::escape(key))
+            .join("|");
+        let end = if trailing_boundary { r"\b" } else { "" };
+
+        match (spaced.is_empty(), unspaced.is_empty()) {
+            (false, true) => format!(r"(?i:\b(?:{spaced}){end})"),
+            (true, false) => format!(r"(?i:{unspaced})"),
+            _ => format!(r"(?i:\b(?:{spaced}){end}|{unspaced})"),
+        }
+    }
+
+    /// - Bare numbers are always chapters, even in books with only one chapter (`Jude 5`), so
+    /// this is only for tests, [`BibleData::parse`](crate::data::data::BibleData::parse) is the
+    /// public one
+    #[cfg(test)]
+    pub(crate) fn parse(&self, input: &str) -> Option<Passage> {
+        self.parse_with(input, &|_, minimal| Segments::from(minimal))
+    }
+
+    /// - A book name with nothing after it (`Ruth`) is the whole book
+    /// - `read` decides what the numbers after the book name mean
+    pub(crate) fn parse_with(
+        &self,
+        input: &str,
+        read: &impl Fn(BookId, MinimalSegments) -> Segments,
+    ) -> Option<Passage> {
+        let m = self.detector.find_books(input).next()?;
+        let book = m.id;
+        // only the line the book name is on
+        let segments = input[m.end()..].split('\n').next().unwrap_or_default();
+        let segments = if segments.trim().is_empty() {
+            Segment::full_book().as_segments()
+        } else {
+            read(book, MinimalSegments::parse(segments)?)
+        };
         Some(segments.with_book(book))
     }
 
+    /// Like `parse`, this is only for tests, see
+    /// [`BibleData::parse_range`](crate::data::data::BibleData::parse_range)
+    #[cfg(test)]
+    pub(crate) fn parse_range(&self, input: &str) -> Option<PassageRange> {
+        self.parse_range_with(input, &|_, minimal| Segments::from(minimal))
+    }
+
+    /**
+    - A range that crosses into a later book (`Gen 50:26–Ex 1:7`, `Matthew 28 - Acts 1`)
+    - The book after the dash must come after the first one
+    - `read` decides what the numbers after each book name mean
+    */
+    pub(crate) fn parse_range_with(
+        &self,
+        input: &str,
+        read: &impl Fn(BookId, MinimalSegments) -> Segments,
+    ) -> Option<PassageRange> {
+        input
+            .char_indices()
+            .filter(|(_, c)| Delimeter::is_range(*c))
+            .find_map(|(idx, c)| {
+                let end = input[idx + c.len_utf8()..].trim_start();
+                let starts_with_book = self
+                    .detector
+                    .find_books(end)
+                    .next()
+                    .is_some_and(|m| m.start() == 0);
+                if !starts_with_book {
+                    return None;
+                }
+                PassageRange::new(
+                    self.parse_with(&input[..idx], read)?,
+                    self.parse_with(end, read)?,
+                )
+            })
+    }
+
+    /// The first book named in the input, even if no chapter or verse follows it
+    pub fn find_book(&self, input: &str) -> Option<BookId> {
+        Some(self.detector.find_books(input).next()?.id)
+    }
+
     pub fn normalize_book_name(name: &str) -> String {
         name.to_lowercase()
             .trim()
@@ -136,11 +377,96 @@ impl Books {
     }
 }
 
+/// Han, Hiragana, and Katakana; Korean puts spaces between words, so it isn't here
+pub(crate) static UNSPACED_SCRIPT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[\p{Han}\p{Hiragana}\p{Katakana}]").unwrap());
+
+/// The ways to write the number of a numbered book (`1 John`, `I John`, `First John`, `1st John`)
+const NUMBER_PREFIXES: [[&str; 4]; 4] = [
+    ["1", "i", "first", "1st"],
+    ["2", "ii", "second", "2nd"],
+    ["3", "iii", "third", "3rd"],
+    ["4", "iv", "fourth", "4th"],
+];
+
+/// - `1 cor`, `1cor`, `i cor`, `first cor`, or `1st cor`, split into the number and `cor`
+/// - Roman numerals and words need a space, so `isa` isn't `i sa`
+static NUMBERED_KEY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(?P<arabic>[1-4])\s*|(?P<word>iv|i{1,3}|first|second|third|fourth|1st|2nd|3rd|4th)\s+)(?P<base>\D.*)$")
+        .unwrap()
+});
+
+/**
+- Every other way of writing the number of a normalized key like `1 cor`
+- The arabic number is generated with and without a space (`1 cor`, `1cor`)
+- Keys that don't start with a book number have no variants
+- Single letter abbreviations (`1 j`) only get arabic variants
+- `i cor` is also the word `I` (`I pet 2 cats`), so the detector only takes it written like `I Cor`
+*/
+fn numbered_variants(key: &str) -> Vec<String> {
+    let Some(caps) = NUMBERED_KEY.captures(key) else {
+        return vec![];
+    };
+    let number = caps
+        .name("arabic")
+        .or(caps.name("word"))
+        .map(|m| m.as_str())
+        .unwrap_or_default();
+    let Some(prefixes) = NUMBER_PREFIXES.iter().find(|p| p.contains(&number)) else {
+        return vec![];
+    };
+    let base = &caps["base"];
+
+    // `i j` and `first p` are more likely to be English than a book
+    let prefixes = if base.chars().count() > 1 {
+        &prefixes[..]
+    } else {
+        &prefixes[..1]
+    };
+    let mut variants: Vec<String> = prefixes.iter().map(|p| format!("{p} {base}")).collect();
+    variants.push(format!("{}{base}", prefixes[0]));
+    variants.retain(|v| v != key);
+    variants
+}
+
+/// The OSIS codes of the default book data, for locales that don't list them
+static DEFAULT_OSIS: Lazy<BTreeMap<BookId, String>> = Lazy::new(|| {
+    BooksInput::default()
+        .0
+        .into_iter()
+        .filter_map(|book| Some((book.id, book.osis?)))
+        .collect()
+});
+
 static DEFAULT_BOOKS: Lazy<Books> = Lazy::new(|| {
     let data = BooksInput::default();
     Books::new(data).expect("The default provided books data should always compile")
 });
 
+/// - Which part of the Bible a book is in
+/// - The deuterocanon (or apocrypha) is its own part, so each canon decides whether to include it
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Testament {
+    #[serde(alias = "ot")]
+    Old,
+    #[serde(alias = "nt")]
+    New,
+    #[serde(alias = "apocrypha")]
+    Deuterocanon,
+}
+
+impl Testament {
+    /// The testament of a Protestant book id, for book data that doesn't say
+    fn from_id(id: BookId) -> Option<Self> {
+        match *id {
+            1..=39 => Some(Testament::Old),
+            40..=66 => Some(Testament::New),
+            _ => None,
+        }
+    }
+}
+
 /**
 Example:
 ```jsonc
@@ -149,6 +475,8 @@ Example:
     "id": 1,
     "book": "Genesis",
     "abbreviation": "Gn",
+    "osis": "Gen",
+    "testament": "old",
     "abbreviations": [
       "gen",
       "ge",
@@ -186,6 +514,16 @@ pub struct Book {
     #[serde(alias = "abbrev")]
     abbreviation: String,
 
+    /// - the OSIS code (`Gen`, `1Cor`), for exchanging references with other Bible software
+    /// - if not provided, the code of the same book id in the default book data
+    #[serde(default)]
+    osis: Option<String>,
+
+    /// - which testament the book is in
+    /// - if not provided, books 1-39 are [`Testament::Old`] and 40-66 are [`Testament::New`]
+    #[serde(default)]
+    testament: Option<Testament>,
+
     /// - does not need to include book name or abbreviation
     /// - meant for matching/parsing references
     #[serde(alias = "abbrs")]
@@ -213,3 +551,126 @@ impl Default for BooksInput {
             .unwrap()
     }
 }
+
+impl BooksInput {
+    /// The bundled book names of `locale`
+    pub fn from_locale(locale: Locale) -> Result<Self, String> {
+        serde_json::from_str(locale.books_json())
+            .map_err(|e| format!("Could not parse the {locale} book file\n{e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{
+        books::{Book, BookId, Books, BooksInput},
+        locale::Locale,
+    };
+
+    #[test]
+    fn numbered_variants() {
+        let books = Books::base();
+        let search = |name: &str| books.search(name);
+
+        assert_eq!(search("First Corinthians"), Some(BookId(46)));
+        assert_eq!(search("II Kgs"), Some(BookId(12)));
+        assert_eq!(search("ii kgs"), Some(BookId(12)));
+        assert_eq!(search("1st Jn"), Some(BookId(62)));
+        assert_eq!(search("Second Peter"), Some(BookId(61)));
+        assert_eq!(search("III John"), Some(BookId(64)));
+        assert_eq!(search("3rd Jn."), Some(BookId(64)));
+        // not a roman numeral
+        assert_eq!(search("isa"), Some(BookId(23)));
+
+        let psg = books.parse("Second Timothy 3:16").unwrap();
+        assert_eq!(psg.book, BookId(55));
+
+        // the word `I` isn't a roman numeral, but `I Pet` is
+        let found: Vec<&str> = books
+            .detector()
+            .find_references(
+                "I pet 2 cats, I sam 3 times, and i cor 1. Read I Pet 2 and I Samuel 3.",
+            )
+            .map(|m| m.as_str())
+            .collect();
+        assert_eq!(found, vec!["I Pet", "I Samuel"]);
+    }
+
+    #[test]
+    fn custom_numbered_variants() {
+        let book = |id: u8, name: &str, abbreviation: &str| Book {
+            id: BookId(id),
+            book: name.to_string(),
+            abbreviation: abbreviation.to_string(),
+            osis: None,
+            testament: None,
+            abbreviations: vec![],
+        };
+        let books = Books::new(BooksInput(vec![
+            book(1, "1 Maccabees", "1 Macc"),
+            book(2, "II Maccabees", "II Macc"),
+        ]))
+        .unwrap();
+
+        assert_eq!(books.search("First Maccabees"), Some(BookId(1)));
+        assert_eq!(books.search("1macc"), Some(BookId(1)));
+        assert_eq!(books.search("2nd Macc"), Some(BookId(2)));
+        assert_eq!(books.search("2 Maccabees"), Some(BookId(2)));
+    }
+
+    #[test]
+    fn locales() {
+        for locale in Locale::ALL {
+            let books = Books::from_locales(&[locale]).unwrap();
+            let protestant = (1..=66).all(|id| books.get_name(BookId(id)).is_some());
+            assert!(protestant, "{locale}");
+        }
+
+        let books = Books::from_locales(&[Locale::English, Locale::Spanish]).unwrap();
+        assert_eq!(books.search("John"), Some(BookId(43)));
+        assert_eq!(books.search("Juan"), Some(BookId(43)));
+        assert_eq!(books.search("Éxodo"), Some(BookId(2)));
+        assert_eq!(books.search("exodo"), Some(BookId(2)));
+        assert_eq!(books.search("2 Reyes"), Some(BookId(12)));
+        assert_eq!(books.search("Segunda Reyes"), None);
+
+        // displayed in the first locale, unless told otherwise
+        let psg = books.parse("Juan 3:16").unwrap();
+        assert_eq!(books.get_name(psg.book).map(String::as_str), Some("John"));
+        let books = books.with_display_locale(Locale::Spanish).unwrap();
+        assert_eq!(books.get_name(psg.book).map(String::as_str), Some("Juan"));
+        assert_eq!(
+            books.get_abbrev(BookId(44)).map(String::as_str),
+            Some("Hch")
+        );
+
+        // `Jn` is John in Spanish and Jonah in Portuguese
+        let es_pt = Books::from_locales(&[Locale::Spanish, Locale::Portuguese]).unwrap();
+        let pt_es = Books::from_locales(&[Locale::Portuguese, Locale::Spanish]).unwrap();
+        assert_eq!(es_pt.search("Jn"), Some(BookId(43)));
+        assert_eq!(pt_es.search("Jn"), Some(BookId(32)));
+
+        let de = Books::from_locales(&[Locale::German]).unwrap();
+        assert_eq!(de.parse("1. Mose 1:1").unwrap().book, BookId(1));
+        assert_eq!(de.parse("Röm 8:28").unwrap().book, BookId(45));
+
+        let ko = Books::from_locales(&[Locale::Korean]).unwrap();
+        let psg = ko.parse("요 3:16").unwrap();
+        assert_eq!(ko.get_name(psg.book).map(String::as_str), Some("요한복음"));
+
+        // no spaces around the name
+        let zh = Books::from_locales(&[Locale::Chinese]).unwrap();
+        let psg = zh.parse("约翰福音3:16").unwrap();
+        assert_eq!(zh.get_name(psg.book).map(String::as_str), Some("约翰福音"));
+        assert_eq!(zh.search("約翰福音"), Some(BookId(43)));
+    }
+
+    #[test]
+    fn locale_codes() {
+        assert_eq!("es".parse::<Locale>(), Ok(Locale::Spanish));
+        assert_eq!("pt-BR".parse::<Locale>(), Ok(Locale::Portuguese));
+        assert_eq!("Deutsch".parse::<Locale>(), Ok(Locale::German));
+        assert_eq!("zh_TW".parse::<Locale>(), Ok(Locale::Chinese));
+        assert!("xx".parse::<Locale>().is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::books::{BookId, Books, Testament};
+
+/// Which books a tradition counts as scripture
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Canon {
+    /// The title as given (for display)
+    name: String,
+    books: BTreeSet<BookId>,
+}
+
+impl Canon {
+    pub fn new(name: impl Into<String>, books: BTreeSet<BookId>) -> Self {
+        Self {
+            name: name.into(),
+            books,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn books(&self) -> &BTreeSet<BookId> {
+        &self.books
+    }
+
+    pub fn contains(&self, book: BookId) -> bool {
+        self.books.contains(&book)
+    }
+}
+
+/// - Every canon, in the order they were given
+/// - The first one is the default
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Canons {
+    canons: Vec<Canon>,
+    /// Title/Abbreviation to the index of the canon
+    input_to_index: BTreeMap<String, usize>,
+}
+
+impl Canons {
+    /// Every book a canon lists must be in `books`
+    pub fn create(books: &Books, input: CanonsInput) -> Result<Self, String> {
+        let mut canons = vec![];
+        let mut input_to_index = BTreeMap::new();
+
+        for (idx, canon) in input.0.into_iter().enumerate() {
+            input_to_index.insert(Self::normalize_key(&canon.title), idx);
+            for ab in &canon.abbreviations {
+                input_to_index.insert(Self::normalize_key(ab), idx);
+            }
+
+            let mut ids: BTreeSet<BookId> = canon
+                .testaments
+                .iter()
+                .flat_map(|testament| books.ids_in(*testament))
+                .collect();
+            for book in &canon.books {
+                let Some(id) = books.search(book) else {
+                    return Err(format!(
+                        "Unknown book `{book}` in the {} canon",
+                        canon.title
+                    ));
+                };
+                ids.insert(id);
+            }
+
+            canons.push(Canon::new(canon.title, ids));
+        }
+
+        Ok(Self {
+            canons,
+            input_to_index,
+        })
+    }
+
+    pub fn get(&self, input: &str) -> Option<&Canon> {
+        let idx = self.input_to_index.get(&Self::normalize_key(input))?;
+        self.canons.get(*idx)
+    }
+
+    /// The first canon given
+    pub fn first(&self) -> Option<&Canon> {
+        self.canons.first()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Canon> {
+        self.canons.iter()
+    }
+
+    pub fn normalize_key(name: &str) -> String {
+        name.to_lowercase().trim().replace(" ", "-")
+    }
+}
+
+impl Default for Canons {
+    fn default() -> Self {
+        Self::create(Books::base(), CanonsInput::default())
+            .expect("The default canons should only name known books")
+    }
+}
+
+/**
+Example:
+```jsonc
+[
+  {
+    "title": "Protestant",
+    "abbreviations": [ "p", "prot" ],
+    "testaments": [ "old", "new" ]
+  },
+  {
+    "title": "Catholic",
+    "abbreviations": [ "c", "rc" ],
+    "testaments": [ "old", "new" ],
+    // books outside of those testaments
+    "books": [ "Tobit", "Judith", "Wisdom of Solomon", "Sirach", "Baruch", "1 Maccabees", "2 Maccabees" ]
+  },
+  // ...
+]
+```
+*/
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CanonInput {
+    title: String,
+    #[serde(default)]
+    abbreviations: Vec<String>,
+    #[serde(default)]
+    testaments: Vec<Testament>,
+    #[serde(default)]
+    books: Vec<String>,
+}
+
+static DEFAULT_CANONS_JSON: &'static str = include_str!("./default_canons.json");
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CanonsInput(Vec<CanonInput>);
+
+impl Default for CanonsInput {
+    fn default() -> Self {
+        serde_json::from_str(&DEFAULT_CANONS_JSON)
+            .map_err(|_| format!("Could not parse default canons file"))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::{
+            books::{BookId, Books},
+            canons::{Canons, CanonsInput},
+            data::BibleData,
+        },
+        filter::filter::BibleFilter,
+        matcher::{location::line_col::LineColLocation, matcher::BibleMatcher},
+    };
+
+    #[test]
+    fn canons() {
+        let books = Books::base();
+        let canons = Canons::default();
+        let count = |name: &str| canons.get(name).map(|c| c.books().len());
+
+        assert_eq!(canons.first().map(|c| c.name()), Some("Protestant"));
+        assert_eq!(count("protestant"), Some(66));
+        assert_eq!(count("Catholic"), Some(73));
+        assert_eq!(count("rc"), Some(73));
+        assert_eq!(count("orthodox"), Some(76));
+        assert_eq!(count("all"), Some(77));
+        assert_eq!(count("mormon"), None);
+
+        let sirach = books.search("Sir").unwrap();
+        assert_eq!(sirach, BookId(70));
+        assert!(canons.get("catholic").unwrap().contains(sirach));
+        assert!(!canons.get("protestant").unwrap().contains(sirach));
+    }
+
+    #[test]
+    fn unknown_book() {
+        let input: CanonsInput = serde_json::from_str(
+            r#"[{ "title": "Apocryphal", "testaments": ["new"], "books": ["Sirach", "Gospel of Thomas"] }]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Canons::create(Books::base(), input).unwrap_err(),
+            "Unknown book `Gospel of Thomas` in the Apocryphal canon"
+        );
+    }
+
+    #[test]
+    fn deuterocanon_matches() {
+        let input = "Sirach 2:1 and Wis 3:1 next to John 3:16";
+
+        let matches = BibleMatcher::default()
+            .search::<LineColLocation>(input)
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+
+        let data = BibleData::default().with_canon("catholic").unwrap();
+        let matcher = BibleFilter::new(data).create_matcher().unwrap();
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+        let found: Vec<String> = matches
+            .iter()
+            .filter_map(|m| matcher.data().format_passage(&m.psg))
+            .collect();
+        assert_eq!(
+            found,
+            vec!["Sirach 2:1", "Wisdom of Solomon 3:1", "John 3:16"]
+        );
+
+        assert!(BibleData::default().with_canon("mormon").is_err());
+    }
+}
@@ -2,7 +2,20 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::data::books::{BookId, Books};
+use crate::{
+    data::books::{BookId, Books},
+    segments::{
+        parser::minimal::MinimalSegments,
+        segment::Segment,
+        segments::{Passage, Segments},
+        units::range_pair::RangePair,
+        verse_bounds::VerseBounds,
+    },
+};
+
+/// - A verse's position in the whole Bible, counting from `0` at the first verse of the first book
+/// - Ranges of these turn any passage into plain integer intervals, which is what indexes want
+pub type VerseOrdinal = u32;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChapterVerses(Vec<u8>);
@@ -20,6 +33,16 @@ impl ChapterVerses {
     pub fn get_chapter_count(&self) -> u8 {
         self.0.len() as u8
     }
+
+    pub fn get_verse_count(&self) -> u32 {
+        self.0.iter().map(|&v| v as u32).sum()
+    }
+
+    /// The number of verses in every chapter before this one
+    pub fn get_verses_before(&self, chapter: u8) -> u32 {
+        let idx = chapter.saturating_sub(1) as usize;
+        self.0.iter().take(idx).map(|&v| v as u32).sum()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -40,11 +63,193 @@ impl BookChapterVerses {
         self.0.get(book)
     }
 
+    /// Whether this book only has one chapter (`Obadiah`, `Philemon`, `2 John`, `3 John`, `Jude`)
+    pub fn has_one_chapter(&self, book: BookId) -> bool {
+        self.0.get(&book).is_some_and(|cv| cv.has_one_chapter())
+    }
+
+    /**
+    - The numbers after a book name, read the way they're meant in that book
+    - In a book with only one chapter, bare numbers are verses and ranges are verse ranges
+    (`Jude 5` is `Jude 1:5`, `Philemon 4-6` is `Philemon 1:4-6`)
+    - `f.`/`ff.` stay inside the book (`Rom 8ff.` is `Rom 8-16`, `Rom 8:39f.` is `Rom 8:39`)
+    */
+    pub fn read_segments(&self, book: BookId, minimal: MinimalSegments) -> Segments {
+        // one segment is read from each minimal segment, so they line up
+        let onwards = minimal
+            .segments
+            .iter()
+            .map(|seg| seg.onward)
+            .collect::<Vec<_>>();
+        let mut segments = if self.has_one_chapter(book) {
+            Segments::from_minimal_in_chapter(minimal, 1)
+        } else {
+            Segments::from(minimal)
+        };
+        if let Some(cv) = self.get_chapter_verses(&book) {
+            for (seg, onward) in segments.iter_mut().zip(onwards) {
+                if let Some(onward) = onward {
+                    *seg = seg.resolve_following(onward, cv);
+                }
+            }
+        }
+        segments
+    }
+
     // pub fn get_last_verse(&self, book: &BookId, chapter: u8) -> Option<u8> {
     //     self.0.get(book)?.get_last_verse(chapter)
     // }
 }
 
+impl BookChapterVerses {
+    /// Every book after `start` and before `end`, in canonical order
+    pub fn books_between(&self, start: BookId, end: BookId) -> impl Iterator<Item = BookId> + '_ {
+        self.0
+            .range(start..end)
+            .map(|(book, _)| *book)
+            .filter(move |book| *book != start)
+    }
+
+    /// The ordinal of the first verse in this book
+    pub fn book_offset(&self, book: BookId) -> Option<VerseOrdinal> {
+        self.0.get(&book)?;
+        Some(
+            self.0
+                .range(..book)
+                .map(|(_, cv)| cv.get_verse_count())
+                .sum(),
+        )
+    }
+
+    /// - Returns [`None`] when the chapter or verse doesn't exist in this book
+    pub fn ordinal(&self, book: BookId, chapter: u8, verse: u8) -> Option<VerseOrdinal> {
+        let cv = self.0.get(&book)?;
+        let last_verse = cv.get_last_verse(chapter)?;
+        if verse == 0 || verse > last_verse {
+            return None;
+        }
+        Some(self.book_offset(book)? + cv.get_verses_before(chapter) + verse as u32 - 1)
+    }
+
+    /**
+    - The (inclusive) ordinal range a segment covers
+    - Open-ended segments (like full chapters) end at the last verse of their ending chapter
+    - Ends past the end of a chapter/book are clamped, so `Jude 1-2` still covers Jude
+    */
+    pub fn ordinal_range(
+        &self,
+        book: BookId,
+        seg: &impl VerseBounds,
+    ) -> Option<RangePair<VerseOrdinal>> {
+        let cv = self.0.get(&book)?;
+        let start = self.ordinal(book, seg.starting_chapter(), seg.starting_verse())?;
+
+        let end_chapter = seg.ending_chapter().min(cv.get_chapter_count());
+        let last_verse = cv.get_last_verse(end_chapter)?;
+        let end_verse = match seg.ending_verse() {
+            Some(verse) if end_chapter == seg.ending_chapter() => verse.min(last_verse),
+            _ => last_verse,
+        };
+        let end = self.ordinal(book, end_chapter, end_verse)?;
+
+        (start <= end).then(|| RangePair::new(start, end))
+    }
+
+    /**
+    - Whether every chapter and verse the segment names exists in this book
+    - Unlike [`BookChapterVerses::ordinal_range`], ends past the end of a chapter/book don't count
+    (`John 3:16-99`), and neither do backwards ranges (`John 3:16-2`)
+    */
+    pub fn contains(&self, book: BookId, seg: &Segment) -> bool {
+        let Some(cv) = self.0.get(&book) else {
+            return false;
+        };
+        if let Segment::FullBook(_) = seg {
+            return true;
+        }
+        let start = self.ordinal(book, seg.starting_chapter(), seg.starting_verse());
+        let end_verse = seg
+            .ending_verse()
+            .or_else(|| cv.get_last_verse(seg.ending_chapter()));
+        let end = end_verse.and_then(|verse| self.ordinal(book, seg.ending_chapter(), verse));
+        matches!((start, end), (Some(start), Some(end)) if start <= end)
+    }
+
+    /// Whether the passage has segments, and every one of them exists
+    pub fn contains_passage(&self, psg: &Passage) -> bool {
+        !psg.segments.is_empty() && psg.segments.iter().all(|seg| self.contains(psg.book, seg))
+    }
+
+    /**
+    - The segment, with its end pulled back to the last chapter/verse that exists (`John 3:16-99`
+    is `John 3:16-36`, `Obadiah 1-3` is `Obadiah 1`)
+    - [`None`] when it starts at a chapter/verse that doesn't exist, since there is nothing to pull
+    it back to
+    */
+    pub fn clamp(&self, book: BookId, seg: &Segment) -> Option<Segment> {
+        if self.contains(book, seg) {
+            return Some(*seg);
+        }
+        let cv = self.0.get(&book)?;
+        let (start_chapter, start_verse) = (seg.starting_chapter(), seg.starting_verse());
+        let start = self.ordinal(book, start_chapter, start_verse)?;
+
+        let end_chapter = seg.ending_chapter().min(cv.get_chapter_count());
+        let last_verse = cv.get_last_verse(end_chapter)?;
+        let end_verse = match seg.ending_verse() {
+            Some(verse) if end_chapter == seg.ending_chapter() => verse.min(last_verse),
+            _ => last_verse,
+        };
+        let end = self.ordinal(book, end_chapter, end_verse)?;
+        if start > end {
+            return None;
+        }
+
+        let clamped = match seg {
+            Segment::FullChapter(_) | Segment::FullChapterRange(_)
+                if start_chapter == end_chapter =>
+            {
+                Segment::full_chapter(start_chapter)
+            }
+            Segment::FullChapter(_) | Segment::FullChapterRange(_) => {
+                Segment::full_chapter_range(start_chapter, end_chapter)
+            }
+            _ => {
+                // the subverse only belongs to the verse it was written on
+                let end_subverse = (Some(end_verse) == seg.ending_verse()
+                    && end_chapter == seg.ending_chapter())
+                .then(|| seg.ending_subverse())
+                .flatten();
+                Segment::chapter_range(start_chapter, start_verse, end_chapter, end_verse)
+                    .with_subverses(seg.starting_subverse(), end_subverse)
+            }
+        };
+        Some(clamped)
+    }
+
+    /// - Every segment clamped, see [`BookChapterVerses::clamp`]
+    /// - [`None`] when none of them exist
+    pub fn clamp_passage(&self, psg: &Passage) -> Option<Passage> {
+        let segments: Vec<Segment> = psg
+            .segments
+            .iter()
+            .filter_map(|seg| self.clamp(psg.book, seg))
+            .collect();
+        (!segments.is_empty()).then(|| Passage {
+            book: psg.book,
+            segments: Segments(segments),
+        })
+    }
+
+    /// One ordinal range per segment (invalid segments are skipped)
+    pub fn passage_ordinals(&self, psg: &Passage) -> Vec<RangePair<VerseOrdinal>> {
+        psg.segments
+            .iter()
+            .filter_map(|seg| self.ordinal_range(psg.book, seg))
+            .collect()
+    }
+}
+
 impl Default for BookChapterVerses {
     fn default() -> Self {
         Self::create(Books::base(), BookChapterVersesInput::default())
@@ -72,3 +277,55 @@ impl Default for BookChapterVersesInput {
             .unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::{books::Books, chapter_verses::BookChapterVerses},
+        segments::units::range_pair::RangePair,
+    };
+
+    #[test]
+    fn ordinals() {
+        let cv = BookChapterVerses::default();
+        let books = Books::base();
+        let ordinals = |s: &str| cv.passage_ordinals(&books.parse(s).unwrap());
+
+        assert_eq!(ordinals("Genesis 1:1"), vec![RangePair::new(0, 0)]);
+        assert_eq!(ordinals("Genesis 1"), vec![RangePair::new(0, 30)]);
+        assert_eq!(ordinals("Genesis 2:1"), vec![RangePair::new(31, 31)]);
+        // the last verse of the Bible
+        assert_eq!(
+            ordinals("Revelation 22:21"),
+            vec![RangePair::new(31101, 31101)]
+        );
+        // verses that don't exist have no ordinal
+        assert_eq!(ordinals("Genesis 1:32"), vec![]);
+    }
+
+    #[test]
+    fn contains_and_clamp() {
+        let cv = BookChapterVerses::default();
+        let books = Books::base();
+        let contains = |s: &str| cv.contains_passage(&books.parse(s).unwrap());
+        let clamp = |s: &str| {
+            cv.clamp_passage(&books.parse(s).unwrap())
+                .map(|psg| psg.segments.to_string())
+        };
+
+        assert!(contains("John 3:16"));
+        assert!(contains("John 21"));
+        assert!(!contains("John 99:1"));
+        assert!(!contains("John 3:99"));
+        assert!(!contains("John 3:16-99"));
+        assert!(!contains("Jude 5:2"));
+
+        assert_eq!(clamp("John 3:16"), Some("3:16".to_string()));
+        assert_eq!(clamp("John 3:16-99"), Some("3:16-36".to_string()));
+        assert_eq!(clamp("John 20-25"), Some("20-21".to_string()));
+        assert_eq!(clamp("John 21:24-23:1"), Some("21:24-25".to_string()));
+        assert_eq!(clamp("John 99:1"), None);
+        assert_eq!(clamp("John 3:99"), None);
+        assert_eq!(clamp("John 99:1,3:16"), Some("3:16".to_string()));
+    }
+}
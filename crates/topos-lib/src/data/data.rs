@@ -1,16 +1,28 @@
-use itertools::Itertools;
 use once_cell::sync::Lazy;
-use regex::Regex;
 
-use crate::{data::chapter_verses::BookChapterVerses, segments::segments::Passage};
+use crate::{
+    data::chapter_verses::BookChapterVerses,
+    segments::{formatter::options::FormatOptions, passage_range::PassageRange, segments::Passage},
+};
 
-use super::{books::Books, genres::Genres};
+use super::{
+    books::{BookId, Books},
+    canons::{Canon, Canons},
+    detector::BookDetector,
+    genres::Genres,
+    locale::Locale,
+    versification::{Versification, Versifications},
+};
 
 #[derive(Clone, Debug)]
 pub struct BibleData {
     books: Books,
     genres: Genres,
     chapter_verses: BookChapterVerses, // testaments: Test
+    versifications: Versifications,
+    canons: Canons,
+    /// The books that are searched unless a filter says otherwise
+    canon: Canon,
 }
 
 impl BibleData {
@@ -26,30 +38,131 @@ impl BibleData {
         &self.chapter_verses
     }
 
-    pub fn create_book_regex(&self) -> Result<Regex, String> {
-        let books_pattern: String = self
-            .books()
-            .iter_keys_and_ids()
-            .map(|(key, id)| (key))
-            .join("|");
+    pub fn canons(&self) -> &Canons {
+        &self.canons
+    }
+
+    pub fn canon(&self) -> &Canon {
+        &self.canon
+    }
 
-        let book_regex = Regex::new(format!(r"\b(((?:)(?i){books_pattern}))\b\.?").as_str())
-            .map_err(|e| format!("Failed to compile book_regex because of bad user input.\n{e}"))?;
+    /// - Search the books of another canon (`catholic`, `orthodox`), see [`Canons`]
+    /// - The default is the first canon in the data
+    pub fn with_canon(mut self, name: &str) -> Result<Self, String> {
+        self.canon = self
+            .canons
+            .get(name)
+            .ok_or(format!("Unknown canon `{name}`"))?
+            .clone();
+        Ok(self)
+    }
+
+    pub fn versifications(&self) -> &Versifications {
+        &self.versifications
+    }
 
-        Ok(book_regex)
+    /// A passage numbered in `from`, numbered in `to`, see [`Versifications::convert`]
+    pub fn convert(&self, psg: &Passage, from: Versification, to: Versification) -> Passage {
+        self.versifications
+            .convert(psg, from, to, &self.chapter_verses)
     }
 
-    // pub fn parse(&self, input: &str) -> Option<BookSegments> {
-    //
-    // }
+    /**
+    - A passage (`Romans 8:28`), or just a book name (`Ruth`) for the whole book
+    - Bare numbers in books with only one chapter are verses (`Jude 5` is `Jude 1:5`), see
+    [`BookChapterVerses::read_segments`]
+    */
+    pub fn parse(&self, input: &str) -> Option<Passage> {
+        self.books.parse_with(input, &|book, minimal| {
+            self.chapter_verses.read_segments(book, minimal)
+        })
+    }
+
+    /// A range that crosses into a later book (`Matthew 28 - Acts 1`), each end read like
+    /// [`BibleData::parse`]
+    pub fn parse_range(&self, input: &str) -> Option<PassageRange> {
+        self.books.parse_range_with(input, &|book, minimal| {
+            self.chapter_verses.read_segments(book, minimal)
+        })
+    }
+
+    /// `Ruth 1:1`, `Jude 5`, or just `Ruth` for the whole book, see [`BibleData::format_passage_with`]
+    pub fn format_passage(&self, psg: &Passage) -> Option<String> {
+        self.format_passage_with(psg, &FormatOptions::default())
+    }
+
+    /// - `Ruth 1:1`, or just `Ruth` for the whole book
+    /// - Verses in books with only one chapter are written without it (see
+    /// [`Segments::format_in_book`](crate::segments::segments::Segments::format_in_book))
+    /// - Every passage that is displayed goes through this, so they are all written the same way
+    pub fn format_passage_with(&self, psg: &Passage, options: &FormatOptions) -> Option<String> {
+        let name = self.books.get_name(psg.book)?;
+        let one_chapter = self.chapter_verses.has_one_chapter(psg.book);
+        let segments = psg.segments.format_in_book(options, one_chapter);
+        Some(if segments.is_empty() {
+            name.to_string()
+        } else {
+            format!("{name} {segments}")
+        })
+    }
+
+    /// `Genesis 50:26 – Exodus 1:7`, with each end like [`BibleData::format_passage`]
+    pub fn format_range(&self, range: &PassageRange) -> Option<String> {
+        Some(format!(
+            "{} – {}",
+            self.format_passage(&range.start)?,
+            self.format_passage(&range.end)?
+        ))
+    }
+
+    /// - Search the book names of every one of `locales`, see [`Books::from_locales`]
+    /// - Names are displayed in the first locale
+    pub fn with_locales(mut self, locales: &[Locale]) -> Result<Self, String> {
+        self.books = Books::from_locales(locales)?;
+        Ok(self)
+    }
+
+    /// Display book names in `locale`, no matter which locales are searched
+    pub fn with_display_locale(mut self, locale: Locale) -> Result<Self, String> {
+        self.books = self.books.with_display_locale(locale)?;
+        Ok(self)
+    }
+
+    /**
+    - Finds the names of the books that pass `include`, see [`BookDetector`]
+    - Every book is already in [`Books::detector`], so this is only for a subset of them
+    */
+    pub fn create_detector(
+        &self,
+        include: impl Fn(&BookId) -> bool,
+    ) -> Result<BookDetector, String> {
+        BookDetector::new(
+            self.books()
+                .iter_keys_and_ids()
+                .filter(|(_, id)| include(id)),
+        )
+    }
 }
 
 impl Default for BibleData {
     fn default() -> Self {
+        let canons = Canons::default();
+        let canon = canons.first().cloned().unwrap_or_else(|| {
+            Canon::new(
+                "All",
+                Books::base()
+                    .iter_ids_and_names()
+                    .map(|(id, _)| *id)
+                    .collect(),
+            )
+        });
         Self {
             books: Default::default(),
             genres: Default::default(),
             chapter_verses: Default::default(),
+            versifications: Default::default(),
+            canons,
+            canon,
         }
     }
 }
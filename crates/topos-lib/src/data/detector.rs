@@ -0,0 +1,472 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Range,
+};
+
+use aho_corasick::{AhoCorasick, Anchored, Input, MatchKind, StartKind};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::data::books::{BookId, UNSPACED_SCRIPT};
+
+/// Regex's `\d`, for the digits that aren't ASCII
+static DIGIT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d$").unwrap());
+
+#[derive(Copy, Clone, Debug)]
+struct Key {
+    id: BookId,
+    /// Chinese and Japanese keys, which don't need a word boundary around them
+    unspaced: bool,
+    /// `i pet`, whose roman numeral is also the word `I` (`I pet 2 cats`), see [`roman_one_fits`]
+    roman_one: bool,
+}
+
+/**
+- Finds book names (every key of [`Books`](crate::data::books::Books)) in text, all at once
+- These are Aho-Corasick automatons (leftmost-longest), which build far faster than an alternation
+of every key, and they are only built once: [`Books::detector`](crate::data::books::Books::detector) has
+every book, and [`BibleFilter::create_detector`](crate::filter::filter::BibleFilter::create_detector)
+only the filtered ones
+- Like the regex it replaces:
+    - Keys are case insensitive
+    - Keys need a word boundary before them, except Chinese and Japanese ones (`在约翰福音3:16中`)
+    - When the longest key at a spot doesn't fit (`1 John said 3`), the shorter ones there are tried,
+    and then the next spot
+- Case folding is simple: characters whose lowercase is a different size in UTF-8 (`İ`) are
+not folded
+*/
+#[derive(Clone, Debug)]
+pub struct BookDetector {
+    automaton: AhoCorasick,
+    /// Every key backwards, for matching them from the chapter number that follows them
+    reversed: AhoCorasick,
+    /// The key of each pattern in both automatons
+    patterns: Vec<Key>,
+    /// In bytes, so the text before a chapter number that could be a key is known
+    longest_key: usize,
+    /// The last character of every key, so most numbers (`8:28`) are skipped without a search
+    key_ends: HashSet<char>,
+    /// Every key, for trying the shorter ones when the longest one doesn't fit
+    keys: HashMap<String, Key>,
+}
+
+impl BookDetector {
+    /// - `keys` must be normalized (see [`Books::normalize_book_name`](crate::data::books::Books::normalize_book_name))
+    pub fn new<'a>(
+        keys: impl IntoIterator<Item = (&'a String, &'a BookId)>,
+    ) -> Result<Self, String> {
+        let keys: BTreeMap<&String, Key> = keys
+            .into_iter()
+            .map(|(key, id)| {
+                let unspaced = UNSPACED_SCRIPT.is_match(key);
+                let roman_one = key.starts_with("i ");
+                (
+                    key,
+                    Key {
+                        id: *id,
+                        unspaced,
+                        roman_one,
+                    },
+                )
+            })
+            .collect();
+
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .ascii_case_insensitive(true)
+            .build(keys.keys())
+            .map_err(|e| format!("Failed to build the book detector.\n{e}"))?;
+        let reversed = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .start_kind(StartKind::Anchored)
+            .ascii_case_insensitive(true)
+            .build(keys.keys().map(|key| key.chars().rev().collect::<String>()))
+            .map_err(|e| format!("Failed to build the book detector.\n{e}"))?;
+
+        Ok(Self {
+            automaton,
+            reversed,
+            patterns: keys.values().copied().collect(),
+            longest_key: keys.keys().map(|key| key.len()).max().unwrap_or_default(),
+            key_ends: keys
+                .keys()
+                .filter_map(|key| key.chars().next_back())
+                .collect(),
+            keys: keys
+                .into_iter()
+                .map(|(key, info)| (key.clone(), info))
+                .collect(),
+        })
+    }
+
+    /// - Every book name with a chapter number after it (`Rom. 8:28`, `John3:16`)
+    /// - This is what written references start with
+    /// - These are found backwards from each number, since numbers are much rarer in notes than
+    /// words that are also short keys (`is`, `am`, `job`)
+    pub fn find_references<'d, 'h>(&'d self, input: &'h str) -> ReferenceMatches<'d, 'h> {
+        ReferenceMatches::new(self, input)
+    }
+
+    /// Every book name that is a whole word (`Rom`, but not in `Romania`), even with nothing after it
+    pub fn find_books<'d, 'h>(&'d self, input: &'h str) -> BookMatches<'d, 'h> {
+        BookMatches::new(self, input)
+    }
+
+    /// The key that `name` is, ignoring ASCII case like the automatons do
+    fn key(&self, name: &str) -> Option<Key> {
+        match self.keys.get(name) {
+            Some(key) => Some(*key),
+            None if name.bytes().any(|b| b.is_ascii_uppercase()) => {
+                self.keys.get(&name.to_ascii_lowercase()).copied()
+            }
+            None => None,
+        }
+    }
+
+    /// - The book name at `start`, which ends at or before `longest`
+    /// - Keys are tried from the longest one
+    fn fit<'h>(
+        &self,
+        input: &'h str,
+        folded: &str,
+        start: usize,
+        longest: (usize, Key),
+    ) -> Option<BookMatch<'h>> {
+        let (longest_end, longest_key) = longest;
+        let shorter = (start + 1..longest_end)
+            .rev()
+            .filter(|end| folded.is_char_boundary(*end))
+            .filter_map(|end| Some((end, self.key(&folded[start..end])?)));
+
+        std::iter::once((longest_end, longest_key))
+            .chain(shorter)
+            .find_map(|(name_end, key)| {
+                if !key.unspaced && !is_word_boundary(input, start) {
+                    return None;
+                }
+                if key.roman_one && !roman_one_fits(&input[start..name_end]) {
+                    return None;
+                }
+                let end = if input[name_end..].starts_with('.') {
+                    name_end + 1
+                } else {
+                    name_end
+                };
+                let fits = key.unspaced || is_word_boundary(input, name_end);
+                fits.then_some(BookMatch {
+                    input,
+                    start,
+                    name_end,
+                    end,
+                    id: key.id,
+                })
+            })
+    }
+}
+
+/// A book name that was found, see [`BookDetector`]
+#[derive(Copy, Clone, Debug)]
+pub struct BookMatch<'h> {
+    input: &'h str,
+    start: usize,
+    /// Where the name ends, before its abbreviation period
+    name_end: usize,
+    end: usize,
+    pub id: BookId,
+}
+
+impl<'h> BookMatch<'h> {
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// After the abbreviation period, if there is one
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The book name as it was written, including its abbreviation period (`Rom.`)
+    pub fn as_str(&self) -> &'h str {
+        &self.input[self.start..self.end]
+    }
+
+    /// The book name as it was written, without its abbreviation period (`Rom`)
+    pub fn name(&self) -> &'h str {
+        &self.input[self.start..self.name_end]
+    }
+}
+
+/// The book names in some text, in order, see [`BookDetector`]
+pub struct BookMatches<'d, 'h> {
+    detector: &'d BookDetector,
+    input: &'h str,
+    /// `input`, with the same length in UTF-8, but non-ASCII letters lowercase
+    folded: Cow<'h, str>,
+    pos: usize,
+}
+
+impl<'d, 'h> BookMatches<'d, 'h> {
+    fn new(detector: &'d BookDetector, input: &'h str) -> Self {
+        Self {
+            detector,
+            input,
+            folded: fold_case(input),
+            pos: 0,
+        }
+    }
+}
+
+impl<'d, 'h> Iterator for BookMatches<'d, 'h> {
+    type Item = BookMatch<'h>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let folded = self.folded.as_ref();
+        while self.pos < folded.len() {
+            let found = self
+                .detector
+                .automaton
+                .find(Input::new(folded).range(self.pos..))?;
+            let start = found.start();
+            let key = self.detector.patterns[found.pattern().as_usize()];
+            if let Some(m) = self
+                .detector
+                .fit(self.input, folded, start, (found.end(), key))
+            {
+                self.pos = m.end;
+                return Some(m);
+            }
+            // try again from the next character
+            self.pos = start + folded[start..].chars().next().map_or(1, char::len_utf8);
+        }
+        None
+    }
+}
+
+/// The book names with a chapter number after them, in order, see [`BookDetector::find_references`]
+pub struct ReferenceMatches<'d, 'h> {
+    detector: &'d BookDetector,
+    input: &'h str,
+    /// `input`, with the same length in UTF-8, but non-ASCII letters lowercase
+    folded: Cow<'h, str>,
+    /// Where to look for the next chapter number
+    scan: usize,
+    /// - After the first digit of the last chapter number, where the next book name can start
+    /// - The digit was part of the match in the regex this replaces, so it can't start a key (`1 John`)
+    pos: usize,
+    /// The text before a chapter number, backwards
+    window: String,
+}
+
+impl<'d, 'h> ReferenceMatches<'d, 'h> {
+    fn new(detector: &'d BookDetector, input: &'h str) -> Self {
+        Self {
+            detector,
+            input,
+            folded: fold_case(input),
+            scan: 0,
+            pos: 0,
+            window: String::new(),
+        }
+    }
+
+    /// - The book name right before the chapter number at `chapter`, if there is one
+    /// - The longest key is tried first, then the shorter ones that end in the same place
+    fn before(&mut self, chapter: usize) -> Option<BookMatch<'h>> {
+        let (input, folded) = (self.input, self.folded.as_ref());
+        // the space before the chapter, and the abbreviation period (`Rom. 8`)
+        let end = input[..chapter].trim_end().len();
+        let name_end = end - usize::from(input[..end].ends_with('.'));
+        let last = folded[..name_end].chars().next_back()?;
+        if !self.detector.key_ends.contains(&last.to_ascii_lowercase()) {
+            return None;
+        }
+
+        let mut window_start = name_end.saturating_sub(self.detector.longest_key);
+        while !folded.is_char_boundary(window_start) {
+            window_start -= 1;
+        }
+        self.window.clear();
+        self.window
+            .extend(folded[window_start..name_end].chars().rev());
+        let found = self
+            .detector
+            .reversed
+            .find(Input::new(&self.window).anchored(Anchored::Yes))?;
+        let longest = (
+            name_end - found.end(),
+            self.detector.patterns[found.pattern().as_usize()],
+        );
+
+        let shorter = (longest.0 + 1..name_end)
+            .filter(|start| folded.is_char_boundary(*start))
+            .filter_map(|start| Some((start, self.detector.key(&folded[start..name_end])?)));
+        std::iter::once(longest)
+            .chain(shorter)
+            .find(|(start, key)| {
+                *start >= self.pos
+                    && (key.unspaced || is_word_boundary(input, *start))
+                    && (!key.roman_one || roman_one_fits(&input[*start..name_end]))
+            })
+            .map(|(start, key)| BookMatch {
+                input,
+                start,
+                name_end,
+                end,
+                id: key.id,
+            })
+    }
+}
+
+impl<'d, 'h> Iterator for ReferenceMatches<'d, 'h> {
+    type Item = BookMatch<'h>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(chapter) = next_number(self.input, self.scan) {
+            self.scan = chapter.end;
+            if let Some(m) = self.before(chapter.start) {
+                self.pos = chapter.start
+                    + self.input[chapter.start..]
+                        .chars()
+                        .next()
+                        .map_or(1, char::len_utf8);
+                return Some(m);
+            }
+        }
+        None
+    }
+}
+
+/**
+- Lowercases the non-ASCII letters of `input` (the automaton already ignores ASCII case)
+- Only letters whose lowercase is the same size in UTF-8 are changed, so every byte index still
+points at the same character
+*/
+fn fold_case(input: &str) -> Cow<'_, str> {
+    if input.is_ascii() {
+        return Cow::Borrowed(input);
+    }
+    let mut folded = String::with_capacity(input.len());
+    let mut rest = input;
+    // the ASCII between them is copied as is
+    while let Some(i) = rest.bytes().position(|b| !b.is_ascii()) {
+        folded.push_str(&rest[..i]);
+        let Some(c) = rest[i..].chars().next() else {
+            break;
+        };
+        let mut lower = c.to_lowercase();
+        match (lower.next(), lower.next()) {
+            (Some(l), None) if l.len_utf8() == c.len_utf8() => folded.push(l),
+            _ => folded.push(c),
+        }
+        rest = &rest[i + c.len_utf8()..];
+    }
+    folded.push_str(rest);
+    Cow::Owned(folded)
+}
+
+/// Like regex's `\d`: any decimal digit, not just ASCII ones (`３`)
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit()
+        || (!c.is_ascii() && c.is_numeric() && DIGIT.is_match(c.encode_utf8(&mut [0; 4])))
+}
+
+/// - The next run of digits (like `\d+`) at or after `from`
+/// - Only non-ASCII characters have to be decoded, so this is much faster than the regex
+fn next_number(input: &str, from: usize) -> Option<Range<usize>> {
+    let bytes = input.as_bytes();
+    let mut start = from;
+    loop {
+        start += bytes[start..]
+            .iter()
+            .position(|b| b.is_ascii_digit() || !b.is_ascii())?;
+        let c = input[start..].chars().next()?;
+        if is_digit(c) {
+            break;
+        }
+        start += c.len_utf8();
+    }
+    let end = input[start..]
+        .char_indices()
+        .find(|(_, c)| !is_digit(*c))
+        .map_or(input.len(), |(i, _)| start + i);
+    Some(start..end)
+}
+
+/// - `I Pet` or `I Samuel`, but not `I pet` or `i sam`, which are the word `I`
+/// - Only for keys that start with the roman numeral `i`
+fn roman_one_fits(name: &str) -> bool {
+    let mut words = name.split_whitespace();
+    words.next() == Some("I")
+        && words
+            .next()
+            .and_then(|word| word.chars().next())
+            .is_some_and(char::is_uppercase)
+}
+
+/// Like regex's `\w`
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Like regex's `\b`: whether one side of `idx` is a word character, and the other isn't
+fn is_word_boundary(input: &str, idx: usize) -> bool {
+    let before = input[..idx].chars().next_back().is_some_and(is_word_char);
+    let after = input[idx..].chars().next().is_some_and(is_word_char);
+    before != after
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{books::Books, locale::Locale};
+
+    fn found(input: &str, references: bool) -> Vec<(&str, u8)> {
+        let books =
+            Books::from_locales(&[Locale::English, Locale::Spanish, Locale::Chinese]).unwrap();
+        let detector = books.detector();
+        let matches: Vec<_> = if references {
+            detector.find_references(input).collect()
+        } else {
+            detector.find_books(input).collect()
+        };
+        matches.iter().map(|m| (m.as_str(), *m.id)).collect()
+    }
+
+    #[test]
+    fn references() {
+        assert_eq!(
+            found("Read Rom. 8:28, 1 Corinthians 13 and john3:16.", true),
+            vec![("Rom.", 45), ("1 Corinthians", 46), ("john", 43)]
+        );
+        // no chapter, or not a whole word
+        assert_eq!(found("Romans is great, Romania 3", true), vec![]);
+        // the longest key doesn't fit, but a shorter one does
+        assert_eq!(
+            found("1 John said 3, then 1 John 3", true),
+            vec![("1 John", 62)]
+        );
+        // a chapter number isn't the start of the next book (`2 Co`), and shorter keys ignore case too
+        assert_eq!(found("NAH 2 CO 1", true), vec![("NAH", 34), ("CO", 51)]);
+        // non-ASCII case, and scripts without spaces
+        assert_eq!(
+            found("ÉXODO 3 y 在约翰福音3:16中", true),
+            vec![("ÉXODO", 2), ("约翰福音", 43)]
+        );
+    }
+
+    #[test]
+    fn books() {
+        let input = "Romans, Rom., Romania, and 1 Corinthians";
+        let names: Vec<&str> = Books::base()
+            .detector()
+            .find_books(input)
+            .map(|m| m.name())
+            .collect();
+        assert_eq!(names, vec!["Romans", "Rom", "1 Corinthians"]);
+        assert_eq!(
+            found("Romans, Rom., Romania", false),
+            vec![("Romans", 45), ("Rom.", 45)]
+        );
+        assert_eq!(found("SONG OF SOLOMONS", false), vec![("SONG", 22)]);
+    }
+}
@@ -64,7 +64,7 @@ impl Genres {
                 BTreeSet::default()
             };
 
-            let genre = Genre::new(key.clone(), ids);
+            let genre = Genre::new(key.clone(), genre.title.clone(), ids);
 
             // use title as the genre key
             genres.insert(key.clone(), genre);
@@ -112,6 +112,11 @@ impl Genres {
         self.genres.get_mut(&key)
     }
 
+    /// Every genre, in the order they were given
+    pub fn iter(&self) -> impl Iterator<Item = (&GenreKey, &Genre)> {
+        self.genres.iter()
+    }
+
     pub fn genre_ids<'a>(&'a self, input: &'_ str) -> Option<&'a BTreeSet<BookId>> {
         Some(&self.get(input)?.books)
     }
@@ -141,12 +146,26 @@ impl Default for Genres {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Genre {
     title: GenreKey,
+    /// The title as given (for display)
+    name: String,
     books: BTreeSet<BookId>,
 }
 
 impl Genre {
-    pub fn new(key: GenreKey, books: BTreeSet<BookId>) -> Self {
-        Self { title: key, books }
+    pub fn new(key: GenreKey, name: String, books: BTreeSet<BookId>) -> Self {
+        Self {
+            title: key,
+            name,
+            books,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn books(&self) -> &BTreeSet<BookId> {
+        &self.books
     }
 }
 
@@ -0,0 +1,87 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/**
+- The languages that have bundled book names
+- Every locale uses the same [`BookId`](crate::data::books::BookId)s, so a passage parsed in one
+can be formatted in another
+*/
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+    Portuguese,
+    German,
+    French,
+    Korean,
+    Chinese,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 7] = [
+        Locale::English,
+        Locale::Spanish,
+        Locale::Portuguese,
+        Locale::German,
+        Locale::French,
+        Locale::Korean,
+        Locale::Chinese,
+    ];
+
+    /// The ISO 639-1 code (`en`, `es`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+            Locale::Portuguese => "pt",
+            Locale::German => "de",
+            Locale::French => "fr",
+            Locale::Korean => "ko",
+            Locale::Chinese => "zh",
+        }
+    }
+
+    /// The bundled book names, in the same format as [`BooksInput`](crate::data::books::BooksInput)
+    pub fn books_json(&self) -> &'static str {
+        match self {
+            Locale::English => include_str!("./default_books.json"),
+            Locale::Spanish => include_str!("./locales/es.json"),
+            Locale::Portuguese => include_str!("./locales/pt.json"),
+            Locale::German => include_str!("./locales/de.json"),
+            Locale::French => include_str!("./locales/fr.json"),
+            Locale::Korean => include_str!("./locales/ko.json"),
+            Locale::Chinese => include_str!("./locales/zh.json"),
+        }
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Either the code (`es`), a region code (`pt-BR`), or the name in English or the language itself
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        let language = s.split(['-', '_']).next().unwrap_or_default();
+        Ok(match language {
+            "en" | "english" => Locale::English,
+            "es" | "spanish" | "español" | "espanol" => Locale::Spanish,
+            "pt" | "portuguese" | "português" | "portugues" => Locale::Portuguese,
+            "de" | "german" | "deutsch" => Locale::German,
+            "fr" | "french" | "français" | "francais" => Locale::French,
+            "ko" | "korean" | "한국어" => Locale::Korean,
+            "zh" | "chinese" | "中文" => Locale::Chinese,
+            _ => Err(format!("Unknown locale `{s}`"))?,
+        })
+    }
+}
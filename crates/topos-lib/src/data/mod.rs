@@ -1,4 +1,9 @@
 pub mod books;
+pub mod canons;
 pub mod chapter_verses;
 pub mod data;
+pub mod detector;
 pub mod genres;
+pub mod locale;
+pub mod osis;
+pub mod versification;
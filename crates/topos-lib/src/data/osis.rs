@@ -0,0 +1,279 @@
+use itertools::Itertools;
+
+use crate::{
+    data::{books::BookId, data::BibleData},
+    segments::{
+        passage_range::PassageRange,
+        segment::Segment,
+        segments::{Passage, Segments},
+        units::full_chapter_verse_range::FullChapterVerseRange,
+        verse_bounds::VerseBounds,
+    },
+};
+
+/// One end of an OSIS reference: `John`, `John.3`, `John.3.16`, or `John.3.16!a`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct OsisId {
+    book: BookId,
+    chapter: Option<u8>,
+    verse: Option<u8>,
+    subverse: Option<char>,
+}
+
+/**
+- [OSIS](https://crosswire.org/osis/) references, the format Bible software exchanges them in
+(`John.3.16`, `John.3.16-John.3.18`, `Gen.1-Gen.2`, `1Cor.13`)
+- The book codes come from the book data (see [`Books::get_osis`](crate::data::books::Books::get_osis)),
+so they are the same in every locale
+- Every [`Passage`] can be written as OSIS and read back the same, including `28ff.`
+(`Rom.8.28-Rom.8`) and subverses (`John.3.16!a`)
+- A passage with more than one segment is a space separated list (`John.3.16 John.3.18`)
+*/
+impl BibleData {
+    /// - One OSIS reference (`John.3.16`, `Gen.1-Gen.2`), or a list of them in the same book
+    /// - A range into another book is a [`BibleData::parse_osis_range`]
+    pub fn parse_osis(&self, input: &str) -> Option<Passage> {
+        let mut book = None;
+        let mut segments = Segments::new();
+        for reference in input.split_whitespace() {
+            let (start, end) = self.parse_osis_ids(reference)?;
+            if start.book != end.book || *book.get_or_insert(start.book) != start.book {
+                return None;
+            }
+            segments.push(self.osis_segment(start, end)?);
+        }
+        Some(segments.with_book(book?))
+    }
+
+    /// An OSIS reference that ends in a later book (`Gen.50.26-Exod.1.7`)
+    pub fn parse_osis_range(&self, input: &str) -> Option<PassageRange> {
+        let (start, end) = self.parse_osis_ids(input.trim())?;
+        let passage = |id: OsisId| -> Option<Passage> {
+            Some(self.osis_segment(id, id)?.as_segments().with_book(id.book))
+        };
+        PassageRange::new(passage(start)?, passage(end)?)
+    }
+
+    /// `John.3.16`, `John.3.16-John.3.18`, or `John.3.16 John.3.18` for more than one segment
+    pub fn format_osis(&self, psg: &Passage) -> Option<String> {
+        let code = self.books().get_osis(psg.book)?;
+        if psg.segments.is_empty() {
+            return Some(code.to_string());
+        }
+        Some(
+            psg.segments
+                .iter()
+                .map(|seg| {
+                    let (start, end) = (osis_start(code, seg), osis_end(code, seg));
+                    if start == end {
+                        start
+                    } else {
+                        format!("{start}-{end}")
+                    }
+                })
+                .join(" "),
+        )
+    }
+
+    /// `Gen.50.26-Exod.1.7`
+    pub fn format_osis_range(&self, range: &PassageRange) -> Option<String> {
+        let start_code = self.books().get_osis(range.start.book)?;
+        let end_code = self.books().get_osis(range.end.book)?;
+        Some(format!(
+            "{}-{}",
+            osis_start(start_code, range.start.segments.first()?),
+            osis_end(end_code, range.end.segments.last()?)
+        ))
+    }
+
+    /// Both ends of one OSIS reference (the same when it isn't a range)
+    fn parse_osis_ids(&self, input: &str) -> Option<(OsisId, OsisId)> {
+        // a work prefix (`KJV:John.3.16`) doesn't change the reference
+        let input = input.rsplit_once(':').map_or(input, |(_, id)| id);
+        match input.split_once('-') {
+            Some((start, end)) => Some((self.parse_osis_id(start)?, self.parse_osis_id(end)?)),
+            None => {
+                let id = self.parse_osis_id(input)?;
+                Some((id, id))
+            }
+        }
+    }
+
+    fn parse_osis_id(&self, input: &str) -> Option<OsisId> {
+        let (input, subverse) = match input.split_once('!') {
+            Some((input, grain)) => {
+                let mut chars = grain.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_lowercase() => (input, Some(c)),
+                    _ => return None,
+                }
+            }
+            None => (input, None),
+        };
+
+        let mut parts = input.split('.');
+        let book = self.books().search_osis(parts.next()?)?;
+        let mut number = || -> Option<Option<u8>> {
+            match parts.next() {
+                Some(part) => part.parse::<u8>().ok().filter(|n| *n > 0).map(Some),
+                None => Some(None),
+            }
+        };
+        let chapter = number()?;
+        let verse = number()?;
+        if parts.next().is_some() || (chapter.is_none() && verse.is_some()) {
+            return None;
+        }
+        if subverse.is_some() && verse.is_none() {
+            return None;
+        }
+        Some(OsisId {
+            book,
+            chapter,
+            verse,
+            subverse,
+        })
+    }
+
+    /// The segment from `start` to `end`, in the same book
+    fn osis_segment(&self, start: OsisId, end: OsisId) -> Option<Segment> {
+        let segment = match (start.chapter, start.verse, end.chapter, end.verse) {
+            (None, None, None, None) => Segment::full_book(),
+            (Some(c1), None, Some(c2), None) if c1 == c2 => Segment::full_chapter(c1),
+            (Some(c1), None, Some(c2), None) if c1 < c2 => Segment::full_chapter_range(c1, c2),
+            (Some(c1), Some(v1), Some(c2), Some(v2)) if (c1, v1) <= (c2, v2) => {
+                Segment::chapter_range(c1, v1, c2, v2)
+            }
+            // `John.3-John.3.18` starts at the beginning of the chapter
+            (Some(c1), None, Some(c2), Some(v2)) if c1 == c2 => {
+                Segment::chapter_verse_range(c1, 1, v2)
+            }
+            (Some(c1), None, Some(c2), Some(v2)) if c1 < c2 => {
+                Segment::FullChapterVerseRange(FullChapterVerseRange::new(c1, c2, v2))
+            }
+            // `Rom.8.28-Rom.8` goes to the end of the chapter
+            (Some(c1), Some(v1), Some(c2), None) if c1 == c2 => {
+                Segment::chapter_verse_onward(c1, v1)
+            }
+            (Some(c1), Some(v1), Some(c2), None) if c1 < c2 => {
+                let last_verse = self
+                    .chapter_verses()
+                    .get_chapter_verses(&start.book)?
+                    .get_last_verse(c2)?;
+                Segment::chapter_range(c1, v1, c2, last_verse)
+            }
+            _ => return None,
+        };
+        Some(segment.with_subverses(start.subverse, end.subverse))
+    }
+}
+
+/// `Book.chapter.verse!subverse`, with only the parts that are there
+fn osis_id(code: &str, chapter: u8, verse: Option<u8>, subverse: Option<char>) -> String {
+    match (verse, subverse) {
+        (Some(verse), Some(subverse)) => format!("{code}.{chapter}.{verse}!{subverse}"),
+        (Some(verse), None) => format!("{code}.{chapter}.{verse}"),
+        (None, _) => format!("{code}.{chapter}"),
+    }
+}
+
+/// Where a segment starts, as an OSIS id
+fn osis_start(code: &str, seg: &Segment) -> String {
+    let verse = match seg {
+        Segment::FullBook(_) => return code.to_string(),
+        Segment::ChapterVerse(_)
+        | Segment::ChapterVerseRange(_)
+        | Segment::ChapterVerseOnward(_)
+        | Segment::ChapterRange(_) => Some(seg.starting_verse()),
+        Segment::FullChapter(_)
+        | Segment::FullChapterRange(_)
+        | Segment::FullChapterVerseRange(_) => None,
+    };
+    osis_id(code, seg.starting_chapter(), verse, seg.starting_subverse())
+}
+
+/// Where a segment ends, as an OSIS id
+fn osis_end(code: &str, seg: &Segment) -> String {
+    if let Segment::FullBook(_) = seg {
+        return code.to_string();
+    }
+    osis_id(
+        code,
+        seg.ending_chapter(),
+        seg.ending_verse(),
+        seg.ending_subverse(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{data::BibleData, locale::Locale};
+
+    #[test]
+    fn parse_osis() {
+        let data = BibleData::default();
+        let parse = |input: &str| {
+            let psg = data.parse_osis(input)?;
+            data.format_passage(&psg)
+        };
+        assert_eq!(parse("John.3.16"), Some("John 3:16".to_string()));
+        assert_eq!(
+            parse("John.3.16-John.3.18"),
+            Some("John 3:16-18".to_string())
+        );
+        assert_eq!(parse("Gen.1-Gen.2"), Some("Genesis 1-2".to_string()));
+        assert_eq!(parse("1Cor.13"), Some("1 Corinthians 13".to_string()));
+        assert_eq!(parse("Ps"), Some("Psalms".to_string()));
+        assert_eq!(parse("KJV:rom.8.28"), Some("Romans 8:28".to_string()));
+        assert_eq!(
+            data.parse_osis("John.3.16 John.3.18"),
+            data.parse("John 3:16, 18")
+        );
+
+        // another book, not a number, or not OSIS
+        assert_eq!(parse("Gen.50.26-Exod.1.7"), None);
+        assert_eq!(parse("John.3.x"), None);
+        assert_eq!(parse("John 3:16"), None);
+        assert_eq!(parse("John.3.18-John.3.16"), None);
+
+        let range = data.parse_osis_range("Gen.50.26-Exod.1.7").unwrap();
+        assert_eq!(
+            data.format_range(&range),
+            Some("Genesis 50:26 – Exodus 1:7".to_string())
+        );
+        assert_eq!(
+            data.format_osis_range(&range),
+            Some("Gen.50.26-Exod.1.7".to_string())
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = BibleData::default()
+            .with_locales(&[Locale::Spanish])
+            .unwrap();
+        for input in [
+            "John.3.16",
+            "John.3.16!a",
+            "John.3.16!b-John.3.18!a",
+            "John.3.16-John.3.18",
+            "John.3.16-John.4.2",
+            "Rom.8.28-Rom.8",
+            "Gen.1",
+            "Gen.1-Gen.2",
+            "Gen.1-Gen.2.3",
+            "Jude",
+            "1Cor.13.4 1Cor.13.7",
+        ] {
+            let psg = data.parse_osis(input).unwrap();
+            assert_eq!(data.format_osis(&psg).as_deref(), Some(input), "{input}");
+        }
+
+        // written references have an OSIS id too, no matter the locale
+        let psg = data.parse("1 Corintios 13:4-7").unwrap();
+        assert_eq!(
+            data.format_osis(&psg),
+            Some("1Cor.13.4-1Cor.13.7".to_string())
+        );
+    }
+}
@@ -0,0 +1,443 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::{
+        books::{BookId, Books},
+        chapter_verses::BookChapterVerses,
+    },
+    segments::{
+        passage_range::PassageRange,
+        segment::Segment,
+        segments::{Passage, Segments},
+        verse_bounds::VerseBounds,
+    },
+};
+
+/**
+- How a tradition numbers its chapters and verses
+- The book data (and so every [`Passage`]) is in [`Versification::English`], the others are
+mapped to and from it
+*/
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Versification {
+    /// - The KJV and most English translations
+    #[default]
+    English,
+    /// - The Hebrew Bible
+    /// - Psalm titles are verse 1, and a few chapters split differently (`Malachi 4` is `3:19-24`)
+    Masoretic,
+    /// - The Greek Old Testament
+    /// - Only the Psalms are mapped: Psalms 9-10 and 114-115 are joined, and 116 and 147 are split
+    Septuagint,
+    /// - Numbers the Psalms like the Septuagint
+    Vulgate,
+}
+
+impl Versification {
+    pub const ALL: [Versification; 4] = [
+        Versification::English,
+        Versification::Masoretic,
+        Versification::Septuagint,
+        Versification::Vulgate,
+    ];
+}
+
+impl Display for Versification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Versification::English => write!(f, "english"),
+            Versification::Masoretic => write!(f, "masoretic"),
+            Versification::Septuagint => write!(f, "septuagint"),
+            Versification::Vulgate => write!(f, "vulgate"),
+        }
+    }
+}
+
+impl FromStr for Versification {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "en" | "eng" | "english" | "kjv" => Self::English,
+            "mt" | "masoretic" | "hebrew" | "bhs" => Self::Masoretic,
+            "lxx" | "septuagint" | "greek" => Self::Septuagint,
+            "vg" | "vul" | "vulgate" | "latin" => Self::Vulgate,
+            _ => Err(format!("Unknown versification `{s}`"))?,
+        })
+    }
+}
+
+/**
+- English `verses` of `chapter` are `to` (and the verses after it) in another versification
+- Verses without a shift have the same number in both
+*/
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Shift {
+    book: String,
+    chapter: u8,
+    verses: (u8, u8),
+    to: (u8, u8),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct VerseShift {
+    chapter: u8,
+    start: u8,
+    end: u8,
+    to_chapter: u8,
+    to_verse: u8,
+}
+
+impl VerseShift {
+    fn len(&self) -> u8 {
+        self.end - self.start
+    }
+    fn contains(&self, chapter: u8, verse: u8) -> bool {
+        self.chapter == chapter && (self.start..=self.end).contains(&verse)
+    }
+    fn contains_other(&self, chapter: u8, verse: u8) -> bool {
+        self.to_chapter == chapter && self.to_verse <= verse && verse - self.to_verse <= self.len()
+    }
+}
+
+/// A chapter and verse
+type Point = (u8, u8);
+
+/// A chapter, and a verse or (for [`None`]) the end of the chapter
+type EndPoint = (u8, Option<u8>);
+
+/**
+- Maps passages between [`Versification`]s
+- Only the verses that differ are stored, everything else is the same in every versification
+*/
+#[derive(Clone, Debug)]
+pub struct Versifications(BTreeMap<(Versification, BookId), Vec<VerseShift>>);
+
+impl Versifications {
+    /// Every shift must name a book in `books`
+    pub fn create(books: &Books, input: VersificationsInput) -> Result<Self, String> {
+        let mut map: BTreeMap<(Versification, BookId), Vec<VerseShift>> = BTreeMap::new();
+        for (versification, shifts) in input.0 {
+            for shift in shifts {
+                let Some(book) = books.search(&shift.book) else {
+                    return Err(format!(
+                        "Unknown book `{}` in the {versification} versification",
+                        shift.book
+                    ));
+                };
+                map.entry((versification, book))
+                    .or_default()
+                    .push(VerseShift {
+                        chapter: shift.chapter,
+                        start: shift.verses.0,
+                        end: shift.verses.1,
+                        to_chapter: shift.to.0,
+                        to_verse: shift.to.1,
+                    });
+            }
+        }
+        Ok(Self(map))
+    }
+
+    fn shifts(&self, versification: Versification, book: BookId) -> &[VerseShift] {
+        self.0
+            .get(&(versification, book))
+            .map(|s| s.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// - An English chapter and verse in `to`
+    /// - The second value is whether only the chapter changed
+    fn from_english(
+        &self,
+        to: Versification,
+        book: BookId,
+        (chapter, verse): Point,
+    ) -> (Point, bool) {
+        match self
+            .shifts(to, book)
+            .iter()
+            .find(|s| s.contains(chapter, verse))
+        {
+            Some(s) => (
+                (s.to_chapter, s.to_verse + (verse - s.start)),
+                s.start == s.to_verse,
+            ),
+            None => ((chapter, verse), true),
+        }
+    }
+
+    /**
+    - A chapter and verse in `from` in English
+    - [`None`] for verses without an English counterpart (like the Hebrew Psalm titles): nothing
+    was shifted to them, and the English verse with their number was shifted somewhere else
+    - The end of a chapter goes to the end of the last English verses in that chapter, if it has
+    any
+    */
+    fn to_english(
+        &self,
+        from: Versification,
+        book: BookId,
+        (chapter, verse): EndPoint,
+    ) -> Option<EndPoint> {
+        let shifts = self.shifts(from, book);
+        let shift = match verse {
+            None => shifts
+                .iter()
+                .filter(|s| s.to_chapter == chapter)
+                .max_by_key(|s| s.to_verse + s.len())
+                .map(|s| (s, s.len())),
+            Some(verse) => shifts
+                .iter()
+                .find(|s| s.contains_other(chapter, verse))
+                .map(|s| (s, verse - s.to_verse)),
+        };
+        match (shift, verse) {
+            (Some((s, offset)), _) => Some((s.chapter, Some(s.start + offset))),
+            (None, Some(verse)) if shifts.iter().any(|s| s.contains(chapter, verse)) => None,
+            (None, _) => Some((chapter, verse)),
+        }
+    }
+
+    /**
+    - Both ends of a span in `from` in English, see [`Versifications::to_english`]
+    - Verses without an English counterpart are left out: a span starts after a Psalm title, and
+    ends with the chapter before one
+    - [`None`] when none of it is in English (`Psalm 3:1` in the Masoretic text is only the title)
+    */
+    fn span_to_english(
+        &self,
+        from: Versification,
+        book: BookId,
+        (chapter, verse): Point,
+        end: EndPoint,
+    ) -> Option<(EndPoint, EndPoint)> {
+        let start = (verse..=u8::MAX)
+            .take_while(|verse| end.0 > chapter || end.1.is_none_or(|end| *verse <= end))
+            .find_map(|verse| self.to_english(from, book, (chapter, Some(verse))))?;
+        let end = match self.to_english(from, book, end) {
+            Some(end) => end,
+            None if end.0 > chapter => self.to_english(from, book, (end.0 - 1, None))?,
+            None => return None,
+        };
+        Some((start, end))
+    }
+
+    /**
+    - The same verses, numbered in `to` instead of `from`
+    - Verses `to` doesn't have are left out, so a passage of only a Psalm title has no segments
+    - `chapter_verses` are the English verse counts, for where whole chapters end
+    - Whole chapters stay whole chapters when only their number changes (`Psalm 23` is `Psalm 22`
+    in the Septuagint), otherwise they become verse ranges (`Malachi 4` is `Malachi 3:19-24` in
+    the Masoretic text)
+    */
+    pub fn convert(
+        &self,
+        psg: &Passage,
+        from: Versification,
+        to: Versification,
+        chapter_verses: &BookChapterVerses,
+    ) -> Passage {
+        if from == to {
+            return psg.clone();
+        }
+        let book = psg.book;
+        let cv = chapter_verses.get_chapter_verses(&book);
+        let last_verse = |chapter: u8| cv.and_then(|cv| cv.get_last_verse(chapter));
+
+        let mut segments = Segments::new();
+        for seg in psg.segments.iter() {
+            if let Segment::FullBook(_) = seg {
+                segments.push(*seg);
+                continue;
+            }
+            let whole_chapters = seg.starting_verse() == 1 && seg.ending_verse().is_none();
+
+            // into English, clamped to the verses English has
+            let end = (seg.ending_chapter(), seg.ending_verse());
+            let (start, end) = if from == Versification::English {
+                ((seg.starting_chapter(), Some(seg.starting_verse())), end)
+            } else {
+                let start = (seg.starting_chapter(), seg.starting_verse());
+                let Some(span) = self.span_to_english(from, book, start, end) else {
+                    continue;
+                };
+                span
+            };
+            let clamp = |(chapter, verse): EndPoint| -> Option<Point> {
+                let verse = match (verse, last_verse(chapter)) {
+                    (Some(verse), Some(last)) => verse.min(last),
+                    (Some(verse), None) => verse,
+                    (None, last) => last?,
+                };
+                Some((chapter, verse))
+            };
+            // where a chapter ends isn't known without its verse counts
+            let (Some(start), Some(end)) = (clamp(start), clamp(end)) else {
+                segments.push(*seg);
+                continue;
+            };
+            let ends_chapter = Some(end.1) == last_verse(end.0);
+
+            // then out of English
+            let ((start, start_same), (end, end_same)) = if to == Versification::English {
+                ((start, true), (end, true))
+            } else {
+                (
+                    self.from_english(to, book, start),
+                    self.from_english(to, book, end),
+                )
+            };
+
+            let converted = if let Segment::ChapterVerseOnward(_) = seg {
+                Segment::chapter_verse_onward(start.0, start.1)
+            } else if whole_chapters && start.1 == 1 && ends_chapter && start_same && end_same {
+                if start.0 == end.0 {
+                    Segment::full_chapter(start.0)
+                } else {
+                    Segment::full_chapter_range(start.0, end.0)
+                }
+            } else {
+                Segment::chapter_range(start.0, start.1, end.0, end.1)
+            };
+            segments.push(converted.with_subverses(seg.starting_subverse(), seg.ending_subverse()));
+        }
+        segments.with_book(book)
+    }
+
+    /// Both ends of a range across books, see [`Versifications::convert`]
+    pub fn convert_range(
+        &self,
+        range: &PassageRange,
+        from: Versification,
+        to: Versification,
+        chapter_verses: &BookChapterVerses,
+    ) -> PassageRange {
+        PassageRange {
+            start: self.convert(&range.start, from, to, chapter_verses),
+            end: self.convert(&range.end, from, to, chapter_verses),
+        }
+    }
+}
+
+impl Default for Versifications {
+    fn default() -> Self {
+        Self::create(Books::base(), VersificationsInput::default())
+            .expect("The default versifications should only name known books")
+    }
+}
+
+/**
+Example:
+```jsonc
+{
+  "masoretic": [
+    { "book": "Malachi", "chapter": 4, "verses": [1, 6], "to": [3, 19] },
+    // ...
+  ],
+  // ...
+}
+```
+*/
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VersificationsInput(BTreeMap<Versification, Vec<Shift>>);
+
+static DEFAULT_VERSIFICATIONS_JSON: &'static str = include_str!("./default_versifications.json");
+
+impl Default for VersificationsInput {
+    fn default() -> Self {
+        serde_json::from_str(&DEFAULT_VERSIFICATIONS_JSON)
+            .map_err(|_| format!("Could not parse default versifications file"))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{
+        books::Books,
+        chapter_verses::BookChapterVerses,
+        data::BibleData,
+        versification::{Versification, Versifications, VersificationsInput},
+    };
+
+    #[test]
+    fn convert() {
+        let books = Books::base();
+        let data = BibleData::default();
+        let chapter_verses = BookChapterVerses::default();
+        let versifications = Versifications::default();
+        let convert = |input: &str, from: Versification, to: Versification| {
+            let psg = books.parse(input).unwrap();
+            let psg = versifications.convert(&psg, from, to, &chapter_verses);
+            data.format_passage(&psg).unwrap()
+        };
+        use Versification::*;
+
+        assert_eq!(convert("Malachi 4:5", English, Masoretic), "Malachi 3:23");
+        assert_eq!(convert("Malachi 4", English, Masoretic), "Malachi 3:19-24");
+        assert_eq!(convert("Malachi 3:23", Masoretic, English), "Malachi 4:5");
+        assert_eq!(convert("Malachi 3", Masoretic, English), "Malachi 3-4");
+        assert_eq!(convert("Joel 2:28-32", English, Masoretic), "Joel 3:1-5");
+        assert_eq!(convert("Psalm 51:1", English, Masoretic), "Psalms 51:3");
+        assert_eq!(convert("Psalm 51:21", Masoretic, English), "Psalms 51:19");
+        assert_eq!(convert("Psalm 51", Masoretic, English), "Psalms 51");
+        // the title has no verse in English
+        let title = books.parse("Psalm 3:1").unwrap();
+        let title = versifications.convert(&title, Masoretic, English, &chapter_verses);
+        assert!(title.segments.is_empty());
+        assert_eq!(convert("Psalm 3:1-3", Masoretic, English), "Psalms 3:1-2");
+        assert_eq!(convert("Psalm 3", Masoretic, English), "Psalms 3");
+        assert_eq!(
+            convert("Psalm 50:22-51:1", Masoretic, English),
+            "Psalms 50:22-23"
+        );
+
+        assert_eq!(convert("Psalm 23", English, Septuagint), "Psalms 22");
+        assert_eq!(convert("Psalm 23:1", English, Vulgate), "Psalms 22:1");
+        assert_eq!(convert("Psalm 10:1", English, Septuagint), "Psalms 9:22");
+        assert_eq!(convert("Psalm 9", Septuagint, English), "Psalms 9-10");
+        assert_eq!(convert("Psalm 50:3", Vulgate, Masoretic), "Psalms 51:3");
+        assert_eq!(convert("Psalm 116:10", English, Septuagint), "Psalms 115:1");
+
+        // the same everywhere
+        assert_eq!(convert("John 3:16", English, Masoretic), "John 3:16");
+        assert_eq!(convert("Genesis 1", Masoretic, Septuagint), "Genesis 1");
+        assert_eq!(
+            convert("Romans 8:28ff.", Vulgate, English),
+            "Romans 8:28ff."
+        );
+    }
+
+    #[test]
+    fn unknown_book() {
+        let input: VersificationsInput = serde_json::from_str(
+            r#"{ "masoretic": [{ "book": "Hezekiah", "chapter": 1, "verses": [1, 2], "to": [1, 3] }] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Versifications::create(Books::base(), input).unwrap_err(),
+            "Unknown book `Hezekiah` in the masoretic versification"
+        );
+    }
+
+    #[test]
+    fn parse_versification() {
+        assert_eq!("kjv".parse::<Versification>(), Ok(Versification::English));
+        assert_eq!("MT".parse::<Versification>(), Ok(Versification::Masoretic));
+        assert_eq!(
+            "lxx".parse::<Versification>(),
+            Ok(Versification::Septuagint)
+        );
+        assert_eq!(
+            "Vulgate".parse::<Versification>(),
+            Ok(Versification::Vulgate)
+        );
+        assert!("nrsv".parse::<Versification>().is_err());
+    }
+}
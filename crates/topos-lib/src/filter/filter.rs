@@ -1,11 +1,9 @@
 use std::collections::BTreeSet;
 
-use itertools::Itertools;
-use regex::Regex;
-
 use crate::{
-    data::{books::BookId, data::BibleData},
+    data::{books::BookId, data::BibleData, detector::BookDetector},
     matcher::{matcher::BibleMatcher, matches::ComplexFilter},
+    segments::segments::Passage,
 };
 
 pub trait IsFilter {
@@ -47,8 +45,8 @@ pub struct BibleFilter {
 
 impl BibleFilter {
     pub fn new(data: BibleData) -> Self {
-        // this should start full
-        let ids = (1..=66).map_into().collect();
+        // this should start with every book in the canon
+        let ids = data.canon().books().clone();
         let has_done_an_inclusion = false;
         let complex_filter = ComplexFilter::default();
         Self {
@@ -106,41 +104,44 @@ impl BibleFilter {
         &self.ids
     }
 
-    /**
-    The problem is that a RegEx isn't enough
-    I need to create/return a struct that contains that regex and the segment regex, so that
-    */
-    pub fn create_regex(&self) -> Result<Regex, String> {
-        let books_pattern: String = self
-            .data
-            .books()
-            .iter_keys_and_ids()
-            .filter_map(|(key, id)| (self.ids.contains(&id).then_some(key)))
-            .join("|");
-
-        // let book_regex = Regex::new(format!(r"\b(((?:)(?i){books_pattern})[A-z]*)\.?").as_str())
-        // I am including a chapter number to reduce false positives on abbreviations
-        let book_regex = Regex::new(format!(r"\b(((?:)(?i){books_pattern})\.?)\s*\d").as_str())
-            .map_err(|e| format!("Failed to compile book_regex because of bad user input.\n{e}"))?;
-
-        Ok(book_regex)
+    /// Finds the names of only the books that pass this filter
+    pub fn create_detector(&self) -> Result<BookDetector, String> {
+        self.data.create_detector(|id| self.ids.contains(id))
     }
 
+    /// A range across books (`Matthew 28 - Acts 1`) counts as every book it covers
     pub fn filter_inside(&mut self, passage: &str) {
-        if let Some(psg) = self.data.books().parse(passage) {
+        for psg in self.parse_passages(passage) {
             self.complex_filter.inside(psg);
         }
     }
 
     pub fn filter_outside(&mut self, passage: &str) {
-        if let Some(psg) = self.data.books().parse(passage) {
+        for psg in self.parse_passages(passage) {
             self.complex_filter.outside(psg);
         }
     }
 
-    pub fn create_matcher(self) -> Result<BibleMatcher, String> {
-        let re = self.create_regex()?;
-        Ok(BibleMatcher::new(self.data, re, self.complex_filter))
+    /// A written passage (`Rom 8`), a range of books (`Genesis 50 – Exodus 2`), or either as OSIS
+    /// (`Rom.8`, `Gen.50-Exod.2`)
+    fn parse_passages(&self, passage: &str) -> Vec<Passage> {
+        let data = &self.data;
+        let range = data
+            .parse_range(passage)
+            .or_else(|| data.parse_osis_range(passage));
+        if let Some(range) = range {
+            return range.passages(data.chapter_verses());
+        }
+        data.parse(passage)
+            .or_else(|| data.parse_osis(passage))
+            .into_iter()
+            .collect()
+    }
+
+    pub fn create_matcher(mut self) -> Result<BibleMatcher, String> {
+        let detector = self.create_detector()?;
+        self.complex_filter.restrict_books(self.ids);
+        Ok(BibleMatcher::new(self.data, detector, self.complex_filter))
     }
 }
 
@@ -160,17 +161,40 @@ impl Default for BibleFilter {
 
 #[cfg(test)]
 mod tests {
-    use crate::filter::{
-        filter::{BibleFilter, Operation},
-        filters::genre::GenreFilter,
+    use crate::{
+        filter::{
+            filter::{BibleFilter, Operation},
+            filters::genre::GenreFilter,
+        },
+        matcher::location::line_col::LineColLocation,
     };
 
     #[test]
-    fn make_regex() {
-        let re = BibleFilter::default()
-            // .add(Operation::Include(GenreFilter::new("Pauline")))
-            .create_regex()
+    fn make_detector() {
+        let detector = BibleFilter::default()
+            .add(Operation::Exclude(GenreFilter::new("gospels")))
+            .create_detector()
             .unwrap();
-        println!(r#"rg "{}""#, re.as_str());
+        let found: Vec<u8> = detector
+            .find_references("John 3:16 and Romans 8:28")
+            .map(|m| *m.id)
+            .collect();
+        assert_eq!(found, vec![45]);
+    }
+
+    #[test]
+    fn inside_single_chapter_book() {
+        let mut filter = BibleFilter::default();
+        filter.filter_inside("Jude 3-4");
+        let matcher = filter.create_matcher().unwrap();
+
+        let input = "Jude 3 and Jude 5, but also Jude 1:4";
+        let found: Vec<String> = matcher
+            .search::<LineColLocation>(input)
+            .unwrap()
+            .iter()
+            .filter_map(|m| matcher.data().format_passage(&m.psg))
+            .collect();
+        assert_eq!(found, vec!["Jude 3", "Jude 4"]);
     }
 }
@@ -1,32 +1,38 @@
 use std::str::FromStr;
 
-use itertools::Itertools;
-
-use crate::{data::books::BookId, filter::filter::IsFilter};
+use crate::{
+    data::{
+        books::{BookId, Books, Testament},
+        data::BibleData,
+    },
+    filter::filter::IsFilter,
+};
 
 #[derive(Copy, Clone, Debug)]
 pub enum TestamentFilter {
     Old,
     New,
+    /// The deuterocanon (or apocrypha): Tobit, Sirach, 1 Maccabees, and so on
+    Deuterocanon,
 }
 
 impl TestamentFilter {
-    pub fn contains(&self, book_id: BookId) -> bool {
+    pub fn testament(&self) -> Testament {
         match self {
-            TestamentFilter::Old => 1 <= *book_id && *book_id <= 39,
-            TestamentFilter::New => 40 <= *book_id && *book_id <= 66,
+            TestamentFilter::Old => Testament::Old,
+            TestamentFilter::New => Testament::New,
+            TestamentFilter::Deuterocanon => Testament::Deuterocanon,
         }
     }
+
+    pub fn contains(&self, books: &Books, book_id: BookId) -> bool {
+        books.get_testament(book_id) == Some(self.testament())
+    }
 }
 
 impl IsFilter for TestamentFilter {
-    fn get_ids(&self, _data: &crate::data::data::BibleData) -> std::collections::BTreeSet<BookId> {
-        match self {
-            TestamentFilter::Old => 1..=39,
-            TestamentFilter::New => 40..=66,
-        }
-        .map_into()
-        .collect()
+    fn get_ids(&self, data: &BibleData) -> std::collections::BTreeSet<BookId> {
+        data.books().ids_in(self.testament()).collect()
     }
 }
 
@@ -37,6 +43,7 @@ impl FromStr for TestamentFilter {
         Ok(match s.trim().to_lowercase().as_str() {
             "n" | "nt" | "new" | "new testament" => Self::New,
             "o" | "ot" | "old" | "old testament" => Self::Old,
+            "d" | "dc" | "deuterocanon" | "deuterocanonical" | "apocrypha" => Self::Deuterocanon,
             _ => Err("Invalid Testament")?,
         })
     }
@@ -101,4 +108,24 @@ mod tests {
         [Operation::Exclude(TestamentFilter::Old)],
         27
     );
+
+    mk_test!(
+        include_deuterocanon_filter,
+        [Operation::Include(TestamentFilter::Deuterocanon)],
+        11
+    );
+
+    #[test]
+    fn canon_start() {
+        let data = crate::data::data::BibleData::default()
+            .with_canon("catholic")
+            .unwrap();
+        let mut filter = crate::filter::filter::BibleFilter::new(data);
+        assert_eq!(filter.ids().len(), 73);
+
+        filter.push(Operation::Exclude(TestamentFilter::New));
+        assert_eq!(filter.ids().len(), 46);
+        filter.push(Operation::Exclude(TestamentFilter::Deuterocanon));
+        assert_eq!(filter.ids().len(), 39);
+    }
 }
@@ -0,0 +1,301 @@
+use line_col::LineColLookup;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    data::books::BookId,
+    matcher::{
+        headings::HeadingContexts,
+        instance::{BibleMatch, Resolution},
+        location::line_col::LineColLocation,
+        matcher::BibleMatcher,
+        options::AnaphoraOptions,
+    },
+    segments::{
+        parser::minimal::MinimalSegments,
+        segments::{Passage, Segments},
+        verse_bounds::VerseBounds,
+    },
+};
+
+/**
+- `ibid.`
+- `v. 16`, `vv. 18-20`, `verse 3`, `verses 4-5` (the number is only there to anchor the match)
+- `4:1` (a chapter and verse, with no book)
+*/
+static BARE_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?P<ibid>ibid\b\.?)|\b(?P<verse>vv\.|v\.|verses|verse)\s*\d|\b(?P<cv>\d{1,3}\s*[:：∶]\s*\d)",
+    )
+    .unwrap()
+});
+
+/// `4:30 pm` is a time, not a reference
+static TIME_SUFFIX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?i:a\.?m\b|p\.?m\b)").unwrap());
+
+/// Where a bare reference gets its book (and maybe chapter) from
+struct Antecedent {
+    book: BookId,
+    chapter: Option<u8>,
+    /// Only `ibid.` needs the whole passage
+    passage: Option<Passage>,
+    resolution: Resolution,
+    /// A bare reference is only as likely as what it's attached to
+    confidence: f32,
+}
+
+/**
+- Finds bare references in the gaps between explicit matches, and attaches them to a book
+- With anaphora, that's the most recently resolved passage (explicit or inferred), as long as it's
+within the window and under the same heading
+- With headings, it's otherwise the book (and chapter) of the heading the reference is under
+- `v.`/`vv.`/`verse` forms need a chapter, `4:1` only borrows the book, and `ibid.` repeats the
+previous passage
+- Every resolved match is marked [`Resolution::Inferred`] or [`Resolution::Heading`]
+- `explicit` must be in order of where they start
+*/
+pub fn resolve_bare_references(
+    matcher: &BibleMatcher,
+    lookup: &LineColLookup,
+    input: &str,
+    explicit: Vec<BibleMatch>,
+) -> Vec<BibleMatch> {
+    let options = matcher.options();
+    let headings = options
+        .headings
+        .then(|| HeadingContexts::parse(matcher.data(), input));
+
+    let mut all = Vec::with_capacity(explicit.len());
+    // the most recently resolved passage, where it ended, and its confidence
+    let mut last: Option<(Passage, usize, f32)> = None;
+    let mut gap_start = 0;
+    let mut explicit = explicit.into_iter().peekable();
+
+    loop {
+        let gap_end = explicit
+            .peek()
+            .map(|m| m.location.bytes.start)
+            .unwrap_or(input.len());
+
+        let mut pos = gap_start;
+        while pos < gap_end {
+            let Some(caps) = BARE_REFERENCE.captures(&input[pos..gap_end]) else {
+                break;
+            };
+            let whole = caps.get(0).unwrap();
+            let start = pos + whole.start();
+            // by default, just skip past this one
+            pos += whole.end();
+
+            let Some(antecedent) =
+                antecedent(options.anaphora, headings.as_ref(), last.as_ref(), start)
+            else {
+                continue;
+            };
+
+            let resolved = if let Some(ibid) = caps.name("ibid") {
+                antecedent
+                    .passage
+                    .map(|psg| (psg, pos - whole.end() + ibid.end()))
+            } else if let Some(prefix) = caps.name("verse") {
+                let numbers_start = pos - whole.end() + prefix.end();
+                antecedent.chapter.and_then(|chapter| {
+                    let minimal = MinimalSegments::parse(&input[numbers_start..gap_end])?;
+                    let end = numbers_start + minimal.len();
+                    let segments = Segments::from_minimal_in_chapter(minimal, chapter);
+                    Some((segments.with_book(antecedent.book), end))
+                })
+            } else {
+                MinimalSegments::parse(&input[start..gap_end])
+                    .filter(|minimal| {
+                        minimal
+                            .segments
+                            .first()
+                            .is_some_and(|seg| seg.explicit_start_verse.is_some())
+                    })
+                    .filter(|minimal| !TIME_SUFFIX.is_match(&input[start + minimal.len()..]))
+                    .map(|minimal| {
+                        let end = start + minimal.len();
+                        (Segments::from(minimal).with_book(antecedent.book), end)
+                    })
+            };
+
+            let Some((psg, end)) = resolved else {
+                continue;
+            };
+            let location = LineColLocation::new(lookup, start, end);
+            let m = BibleMatch::new(location, psg.book, psg.segments.clone())
+                .with_resolution(antecedent.resolution)
+                .with_confidence(antecedent.confidence);
+            all.push(m);
+            last = Some((psg, end, antecedent.confidence));
+            pos = end;
+        }
+
+        let Some(m) = explicit.next() else {
+            break;
+        };
+        gap_start = m.location.bytes.end;
+        // a range across books continues from the book it ends in
+        let psg = m.range.as_ref().map_or(&m.psg, |range| &range.end);
+        last = Some((psg.clone(), m.location.bytes.end, m.confidence));
+        all.push(m);
+    }
+
+    all
+}
+
+/// - The previous passage wins if it's close enough and no heading came between them
+/// - Otherwise, fall back to the heading
+fn antecedent(
+    anaphora: Option<AnaphoraOptions>,
+    headings: Option<&HeadingContexts>,
+    last: Option<&(Passage, usize, f32)>,
+    start: usize,
+) -> Option<Antecedent> {
+    let heading = headings.and_then(|headings| headings.at(start));
+
+    if let (Some(anaphora), Some((prev, prev_end, confidence))) = (anaphora, last) {
+        let in_window = start.saturating_sub(*prev_end) <= anaphora.window;
+        let same_section = heading.is_none_or(|ctx| *prev_end >= ctx.start);
+        if in_window && same_section {
+            return Some(Antecedent {
+                book: prev.book,
+                chapter: prev.segments.last().map(|seg| seg.ending_chapter()),
+                passage: Some(prev.clone()),
+                resolution: Resolution::Inferred,
+                confidence: *confidence,
+            });
+        }
+    }
+
+    heading.map(|ctx| Antecedent {
+        book: ctx.book,
+        chapter: ctx.chapter,
+        // `ibid.` only refers back to a citation
+        passage: None,
+        resolution: Resolution::Heading(ctx.heading.clone()),
+        // the heading was written on purpose
+        confidence: 1.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::{
+        instance::Resolution,
+        location::line_col::LineColLocation,
+        matcher::BibleMatcher,
+        options::{AnaphoraOptions, MatcherOptions},
+    };
+
+    #[test]
+    fn resolves_bare_references() {
+        let matcher = BibleMatcher::default()
+            .with_options(MatcherOptions::default().with_anaphora(AnaphoraOptions::default()));
+        let input =
+            "Romans 5:8 says it, and again in 6:23, especially v. 23b and vv. 18-20. See ibid.";
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+
+        let found: Vec<(String, Resolution)> = matches
+            .iter()
+            .map(|m| (m.psg.segments.to_string(), m.resolution.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("5:8".to_string(), Resolution::Explicit),
+                ("6:23".to_string(), Resolution::Inferred),
+                ("6:23b".to_string(), Resolution::Inferred),
+                ("6:18-20".to_string(), Resolution::Inferred),
+                ("6:18-20".to_string(), Resolution::Inferred),
+            ]
+        );
+        assert!(matches.iter().all(|m| *m.psg.book == 45));
+    }
+
+    #[test]
+    fn off_by_default() {
+        let matcher = BibleMatcher::default();
+        let matches = matcher
+            .search::<LineColLocation>("Romans 5:8 and 6:23")
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+    }
+
+    #[test]
+    fn headings() {
+        let matcher =
+            BibleMatcher::default().with_options(MatcherOptions::default().with_headings());
+        let input = "# Romans 8\n\nSee v. 28 and 5:8.\n\n# John\n\nIn 3:16, ibid. and v. 17\n";
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+
+        let found: Vec<(u8, String, Resolution)> = matches
+            .iter()
+            .map(|m| {
+                (
+                    *m.psg.book,
+                    m.psg.segments.to_string(),
+                    m.resolution.clone(),
+                )
+            })
+            .collect();
+        let heading = |h: &str| Resolution::Heading(h.to_string());
+        assert_eq!(
+            found,
+            vec![
+                // the heading itself is a reference
+                (45, "8".to_string(), Resolution::Explicit),
+                (45, "8:28".to_string(), heading("# Romans 8")),
+                (45, "5:8".to_string(), heading("# Romans 8")),
+                // without anaphora there's nothing for `ibid.`, and `# John` has no chapter for `v. 17`
+                (43, "3:16".to_string(), heading("# John")),
+            ]
+        );
+    }
+
+    #[test]
+    fn anaphora_stops_at_headings() {
+        let matcher = BibleMatcher::default().with_options(
+            MatcherOptions::default()
+                .with_anaphora(AnaphoraOptions::default())
+                .with_headings(),
+        );
+        let input = "# John 3\n\nRomans 5:8\n\n## Notes\n\nv. 16, then 4:1\n";
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+
+        let found: Vec<(u8, String, Resolution)> = matches
+            .iter()
+            .map(|m| {
+                (
+                    *m.psg.book,
+                    m.psg.segments.to_string(),
+                    m.resolution.clone(),
+                )
+            })
+            .collect();
+        // `## Notes` is still under John 3, and the Romans citation came before it
+        assert_eq!(
+            found,
+            vec![
+                (43, "3".to_string(), Resolution::Explicit),
+                (45, "5:8".to_string(), Resolution::Explicit),
+                (
+                    43,
+                    "3:16".to_string(),
+                    Resolution::Heading("# John 3".to_string())
+                ),
+                (43, "4:1".to_string(), Resolution::Inferred),
+            ]
+        );
+    }
+
+    #[test]
+    fn outside_window() {
+        let matcher = BibleMatcher::default()
+            .with_options(MatcherOptions::default().with_anaphora(AnaphoraOptions::new(10)));
+        let input = "Romans 5:8 is where we start, and much later we get to 6:23";
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+        assert_eq!(matches.len(), 1);
+    }
+}
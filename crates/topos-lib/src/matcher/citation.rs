@@ -0,0 +1,258 @@
+use std::{fmt::Display, str::FromStr};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::matcher::instance::BibleMatch;
+
+/// How far back (in characters) a marker can be from the reference it is for
+const MARKER_WINDOW: usize = 48;
+/// How far back (in characters) an open parenthesis can be from the reference inside it
+const PARENTHESES_WINDOW: usize = 500;
+
+/// - `cf.`, `see also`, `contra`, `quoted in`, right before the reference
+/// - An open parenthesis/bracket may come between them (`cf. (Rom 8:28)`)
+static MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)(?:^|[^\p{L}])(but\s+see|but\s+cf\.?|see\s+also|see|cf\.?|cp\.|compare|contra|quoting|quoted\s+(?:in|by)|cited\s+in)[\s:,]*[(\[]?\s*$",
+    )
+    .unwrap()
+});
+
+/// A reference in a list right after another one (`cf. Rom 8:28; 1 Cor 2:9`) is cited the same way
+static LIST_SEPARATOR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^[\s,;]*(?:and\s+|or\s+|&\s*)?$").unwrap());
+
+/// A Markdown (`[^1]:`) or Org (`[fn:1]`) footnote definition
+static FOOTNOTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*\[(?:\^[^\]]+\]:|fn:[^\]]*\])").unwrap());
+
+/// What a reference is cited for, from the marker before it
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Role {
+    /// Nothing before it, so it is the text being cited
+    #[default]
+    Primary,
+    /// `cf.`, `compare`, `see`, `see also`
+    CrossReference,
+    /// `contra`, `but see`
+    Contrast,
+    /// `quoting`, `quoted in`, `cited in`
+    Quotation,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [
+        Role::Primary,
+        Role::CrossReference,
+        Role::Contrast,
+        Role::Quotation,
+    ];
+
+    /// The role of a marker, as it was written (`Cf.`, `see  also`)
+    fn of_marker(marker: &str) -> Self {
+        let marker = marker.to_lowercase();
+        let words: Vec<&str> = marker.split_whitespace().collect();
+        match words.as_slice() {
+            ["but", ..] | ["contra"] => Role::Contrast,
+            ["quoting"] | ["quoted", ..] | ["cited", ..] => Role::Quotation,
+            _ => Role::CrossReference,
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Primary => write!(f, "primary"),
+            Role::CrossReference => write!(f, "cross-reference"),
+            Role::Contrast => write!(f, "contrast"),
+            Role::Quotation => write!(f, "quotation"),
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "primary" | "direct" => Role::Primary,
+            "cross-reference" | "cross_reference" | "crossreference" | "xref" | "cf" | "cf."
+            | "see" => Role::CrossReference,
+            "contrast" | "contra" => Role::Contrast,
+            "quotation" | "quote" | "quoting" => Role::Quotation,
+            _ => Err(format!("Unknown role `{s}`"))?,
+        })
+    }
+}
+
+/// Where the reference is in the text around it
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Placement {
+    #[default]
+    Inline,
+    /// Inside parentheses (`(cf. Rom 8:28)`)
+    Parenthetical,
+    /// In a Markdown (`[^1]: Rom 8:28`) or Org (`[fn:1] Rom 8:28`) footnote
+    Footnote,
+}
+
+impl Display for Placement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Placement::Inline => write!(f, "inline"),
+            Placement::Parenthetical => write!(f, "parenthetical"),
+            Placement::Footnote => write!(f, "footnote"),
+        }
+    }
+}
+
+/// How a reference was cited
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Citation {
+    pub role: Role,
+    /// The marker before it, as it was written (`cf.`, `See also`)
+    pub marker: Option<String>,
+    pub placement: Placement,
+}
+
+/**
+- Looks back from the start of every match for how it was cited (see [`Citation`])
+- A match right after another one, with only a list separator between them (`cf. Rom 8:28; 1 Cor
+2:9`), has the same marker
+- `matches` must be in order of where they start, and so is the output
+*/
+pub fn with_citations(input: &str, mut matches: Vec<BibleMatch>) -> Vec<BibleMatch> {
+    let mut prev: Option<(usize, Option<String>)> = None;
+    for m in matches.iter_mut() {
+        let (start, end) = (m.location.bytes.start, m.location.bytes.end);
+        let line_start = input[..start].rfind('\n').map_or(0, |idx| idx + 1);
+
+        // only look back as far as the previous match
+        let mut from = nth_char_back(input, start, MARKER_WINDOW).max(line_start);
+        if let Some((prev_end, _)) = prev {
+            from = from.max(prev_end.min(start));
+        }
+        let before = &input[from..start];
+
+        let marker = match (MARKER.captures(before), &prev) {
+            (Some(caps), _) => Some(caps[1].to_string()),
+            (None, Some((prev_end, prev_marker)))
+                if *prev_end <= start && LIST_SEPARATOR.is_match(&input[*prev_end..start]) =>
+            {
+                prev_marker.clone()
+            }
+            _ => None,
+        };
+
+        let placement = if FOOTNOTE.is_match(&input[line_start..start]) {
+            Placement::Footnote
+        } else if in_parentheses(input, start) {
+            Placement::Parenthetical
+        } else {
+            Placement::Inline
+        };
+
+        m.citation = Citation {
+            role: marker.as_deref().map_or(Role::Primary, Role::of_marker),
+            marker: marker.clone(),
+            placement,
+        };
+        prev = Some((end, marker));
+    }
+    matches
+}
+
+/// The byte index `n` characters before `idx` (or `0`)
+fn nth_char_back(input: &str, idx: usize, n: usize) -> usize {
+    input[..idx]
+        .char_indices()
+        .rev()
+        .nth(n.saturating_sub(1))
+        .map_or(0, |(i, _)| i)
+}
+
+/// - Whether there is an open parenthesis before `idx` that isn't closed before it
+/// - Only the current paragraph is checked
+fn in_parentheses(input: &str, idx: usize) -> bool {
+    let from = input[..idx].rfind("\n\n").map_or(0, |i| i + 2);
+    let from = from.max(nth_char_back(input, idx, PARENTHESES_WINDOW));
+    let mut depth = 0usize;
+    for c in input[from..idx].chars().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' if depth == 0 => return true,
+            '(' => depth -= 1,
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::{
+        citation::{Placement, Role},
+        location::line_col::LineColLocation,
+        matcher::BibleMatcher,
+        options::MatcherOptions,
+    };
+
+    #[test]
+    fn citations() {
+        let input = [
+            "Romans 8:28 is clear (cf. Gen 50:20; Eph 1:11), contra Job 3:3.",
+            "See also John 3:16 and 1 John 4:9, quoted in Heb 1:5.",
+            "[^1]: Compare Ps 2:7.",
+        ]
+        .join("\n");
+        let matches = BibleMatcher::default()
+            .search::<LineColLocation>(&input)
+            .unwrap();
+        let found: Vec<(Role, Option<&str>, Placement)> = matches
+            .iter()
+            .map(|m| {
+                let c = &m.citation;
+                (c.role, c.marker.as_deref(), c.placement)
+            })
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (Role::Primary, None, Placement::Inline),
+                (Role::CrossReference, Some("cf."), Placement::Parenthetical),
+                (Role::CrossReference, Some("cf."), Placement::Parenthetical),
+                (Role::Contrast, Some("contra"), Placement::Inline),
+                (Role::CrossReference, Some("See also"), Placement::Inline),
+                (Role::CrossReference, Some("See also"), Placement::Inline),
+                (Role::Quotation, Some("quoted in"), Placement::Inline),
+                (Role::CrossReference, Some("Compare"), Placement::Footnote),
+            ]
+        );
+
+        let matcher = BibleMatcher::default()
+            .with_options(MatcherOptions::default().with_roles([Role::Primary, Role::Contrast]));
+        let matches = matcher.search::<LineColLocation>(&input).unwrap();
+        let found: Vec<String> = matches
+            .iter()
+            .filter_map(|m| matcher.data().format_passage(&m.psg))
+            .collect();
+        assert_eq!(found, vec!["Romans 8:28", "Job 3:3"]);
+    }
+
+    #[test]
+    fn parse_role() {
+        assert_eq!("cf".parse::<Role>(), Ok(Role::CrossReference));
+        assert_eq!("Contra".parse::<Role>(), Ok(Role::Contrast));
+        assert_eq!("primary".parse::<Role>(), Ok(Role::Primary));
+        assert!("footnote".parse::<Role>().is_err());
+    }
+}
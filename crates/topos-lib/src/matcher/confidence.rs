@@ -0,0 +1,177 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    data::{books::Books, data::BibleData},
+    segments::segments::Passage,
+};
+
+/// Where every score starts, before the signals push it up or down
+const BASE: f32 = 0.5;
+const CAPITALIZED: f32 = 0.15;
+const LOWERCASE: f32 = -0.15;
+const ABBREVIATION_PERIOD: f32 = 0.1;
+const EXPLICIT_VERSE: f32 = 0.2;
+const VALID_VERSES: f32 = 0.15;
+const INVALID_VERSES: f32 = -0.35;
+const AMBIGUOUS_KEY: f32 = -0.2;
+const UNIT_WORD: f32 = -0.3;
+
+/// Book keys that are also everyday words (`Job 3 was tough`, `Acts 2 of the play`)
+const AMBIGUOUS_KEYS: [&str; 22] = [
+    "ac", "act", "acts", "am", "cant", "co", "col", "de", "es", "ex", "ho", "is", "job", "judges",
+    "la", "mar", "mark", "na", "numbers", "ps", "so", "song",
+];
+
+/// - Words that make the number before them a quantity or a time, not a chapter (`Mark 2 points`)
+/// - Only the first few words after a reference are checked, and a sentence break stops it
+static UNIT_WORDS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^\s*[ap]\.?m\b|^[^.!?;\n]{0,24}?\b(points?|pts|minutes?|mins?|seconds?|secs?|hours?|hrs?|days?|weeks?|months?|years?|times|percent|degrees|dollars|pages?|people)\b",
+    )
+    .unwrap()
+});
+
+/**
+- What a reference looked like in the text, to score how likely it is to really be one
+- Only references with a written book name are scored, bare references take the score of what
+they were resolved from
+*/
+#[derive(Copy, Clone, Debug)]
+pub struct Evidence<'a> {
+    /// The book name as it was written, including its abbreviation period
+    pub book: &'a str,
+    pub psg: &'a Passage,
+    /// Whether there was a verse after the chapter (`3:16`, not just `3`)
+    pub explicit_verse: bool,
+    /// Everything after the reference
+    pub after: &'a str,
+}
+
+impl Evidence<'_> {
+    /**
+    - A score from `0.0` to `1.0`
+    - `Romans 8:28` and `Rom. 8:28` are `1.0`, `Romans 8` is `0.8`, `Job 3` is `0.6`, and
+    `job 3` or `Mark 2 points` are `0.3`
+    - A chapter and verse (`Job 3:1`, `Mark 2:5 points`) is never taken for an everyday word
+    */
+    pub fn score(&self, data: &BibleData) -> f32 {
+        let mut score = BASE;
+
+        // scripts without case (`요한복음`, `约翰福音`) count as neither
+        let first_letter = self.book.chars().find(|c| c.is_alphabetic());
+        score += match first_letter {
+            Some(c) if c.is_uppercase() => CAPITALIZED,
+            Some(c) if !c.is_lowercase() => 0.0,
+            _ => LOWERCASE,
+        };
+
+        if self.book.trim_end().ends_with('.') {
+            score += ABBREVIATION_PERIOD;
+        }
+
+        if self.explicit_verse {
+            score += EXPLICIT_VERSE;
+        }
+
+        let valid =
+            data.chapter_verses().passage_ordinals(self.psg).len() == self.psg.segments.len();
+        score += if valid { VALID_VERSES } else { INVALID_VERSES };
+
+        if !self.explicit_verse {
+            let key = Books::normalize_book_name(self.book);
+            if AMBIGUOUS_KEYS.contains(&key.as_str()) {
+                score += AMBIGUOUS_KEY;
+            }
+
+            if UNIT_WORDS.is_match(self.after) {
+                score += UNIT_WORD;
+            }
+        }
+
+        score.clamp(0.0, 1.0)
+    }
+}
+
+/**
+- The confidence of a reference after its passage was renumbered (see
+[`Versifications`](crate::data::versification::Versifications)), since whether its verses exist
+can change
+- Everything else that went into the score is the same
+*/
+pub fn revalidate(confidence: f32, before: &Passage, after: &Passage, data: &BibleData) -> f32 {
+    let valid =
+        |psg: &Passage| data.chapter_verses().passage_ordinals(psg).len() == psg.segments.len();
+    let change = match (valid(before), valid(after)) {
+        (false, true) => VALID_VERSES - INVALID_VERSES,
+        (true, false) => INVALID_VERSES - VALID_VERSES,
+        _ => return confidence,
+    };
+    (confidence + change).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::data::BibleData,
+        matcher::{
+            confidence::Evidence, location::line_col::LineColLocation, matcher::BibleMatcher,
+            options::MatcherOptions,
+        },
+        segments::segments::{Passage, Segments},
+    };
+
+    fn score(book: &str, segments: &str, explicit_verse: bool, after: &str) -> f32 {
+        let data = BibleData::default();
+        let book_id = data.books().search(book).unwrap();
+        let psg: Passage = Segments::parse(segments).unwrap().with_book(book_id);
+        let score = Evidence {
+            book,
+            psg: &psg,
+            explicit_verse,
+            after,
+        }
+        .score(&data);
+        // so the float math doesn't make the comparisons flaky
+        (score * 100.0).round() / 100.0
+    }
+
+    #[test]
+    fn confidence() {
+        assert_eq!(score("Romans", "8:28", true, " says"), 1.0);
+        assert_eq!(score("Rom.", "8:28", true, ""), 1.0);
+        assert_eq!(score("Romans", "8", false, ""), 0.8);
+        assert_eq!(score("Job", "3", false, " was tough"), 0.6);
+        assert_eq!(score("Col.", "3", false, ""), 0.7);
+        assert_eq!(score("job", "3", false, ""), 0.3);
+        assert_eq!(score("is", "40", false, " enough"), 0.3);
+        assert_eq!(score("Mark", "2", false, " points behind"), 0.3);
+        assert_eq!(score("Acts", "2", false, " of the play"), 0.6);
+        assert_eq!(score("Genesis", "99", false, ""), 0.3);
+
+        // a verse makes it a reference, whatever the words around it are
+        assert_eq!(score("Job", "3:1", true, ""), 1.0);
+        assert_eq!(score("Mark", "2:5", true, " points to this"), 1.0);
+        // and `of the` is too common after real references to count against them
+        assert_eq!(score("Romans", "8", false, " of the letter"), 0.8);
+    }
+
+    #[test]
+    fn min_confidence() {
+        let input = "Job 3 was tough, but Rom. 8:28 helped. We were down by Mark 2 points.";
+
+        let matches = BibleMatcher::default()
+            .search::<LineColLocation>(input)
+            .unwrap();
+        assert_eq!(matches.len(), 3);
+
+        let matcher = BibleMatcher::default()
+            .with_options(MatcherOptions::default().with_min_confidence(0.7));
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+        let found: Vec<String> = matches
+            .iter()
+            .map(|m| format!("{} {}", *m.psg.book, m.psg.segments))
+            .collect();
+        assert_eq!(found, vec!["45 8:28".to_string()]);
+    }
+}
@@ -0,0 +1,205 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    data::{books::BookId, data::BibleData},
+    segments::verse_bounds::VerseBounds,
+};
+
+/// `# Romans 8`, with the level and the text
+static MARKDOWN_HEADING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(#{1,6})[ \t]+(.+?)[ \t#]*$").unwrap());
+
+/// `** Romans 8`, with the level and the text
+static ORG_HEADING: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\*+)[ \t]+(.+?)[ \t]*$").unwrap());
+
+/// `book: Romans` in front matter
+static FRONT_MATTER_BOOK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^book[ \t]*:[ \t]*(.+?)[ \t]*$").unwrap());
+
+/// `## Chapter 8`, under a heading that already named the book
+static CHAPTER_HEADING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^(?:chapter|ch\.?)[ \t]*(\d{1,3})\b").unwrap());
+
+/// The book (and maybe chapter) that document structure gives everything after `start`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeadingContext {
+    /// The byte where this context starts (the start of the heading line)
+    pub start: usize,
+    /// The heading (or front matter line) that set the book or chapter
+    pub heading: String,
+    pub book: BookId,
+    pub chapter: Option<u8>,
+}
+
+/// What a single heading says, before it is combined with the headings above it
+#[derive(Clone, Debug)]
+struct HeadingEntry {
+    level: usize,
+    heading: String,
+    book: Option<BookId>,
+    chapter: Option<u8>,
+}
+
+/**
+- The implicit book/chapter contexts of a document, in order
+- Markdown (`#`) and Org (`*`) headings are scoped: a heading only replaces the headings at its
+level or deeper, so `## Application` under `# Romans` keeps Romans
+- A front matter `book:` key applies to the whole document, under every heading
+*/
+#[derive(Clone, Debug, Default)]
+pub struct HeadingContexts(Vec<HeadingContext>);
+
+impl HeadingContexts {
+    pub fn parse(data: &BibleData, input: &str) -> Self {
+        let mut contexts = vec![];
+        let mut stack: Vec<HeadingEntry> = vec![];
+
+        let mut offset = 0;
+        let mut in_front_matter = false;
+        let mut in_code_block = false;
+        for (idx, line) in input.split_inclusive('\n').enumerate() {
+            let start = offset;
+            offset += line.len();
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if idx == 0 && line.trim_end() == "---" {
+                in_front_matter = true;
+                continue;
+            }
+            if in_front_matter {
+                if line.trim_end() == "---" || line.trim_end() == "..." {
+                    in_front_matter = false;
+                } else if let Some(caps) = FRONT_MATTER_BOOK.captures(line) {
+                    let value = caps[1].trim_matches(['"', '\'']);
+                    stack.push(Self::entry(data, 0, line.trim(), value, None));
+                    contexts.extend(Self::effective(&stack, start));
+                }
+                continue;
+            }
+
+            // `# comments` in code aren't headings
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block {
+                continue;
+            }
+
+            let Some(caps) = MARKDOWN_HEADING
+                .captures(line)
+                .or_else(|| ORG_HEADING.captures(line))
+            else {
+                continue;
+            };
+            let level = caps[1].len();
+            let parent_book = stack.iter().rev().find_map(|e| e.book);
+
+            stack.retain(|e| e.level < level);
+            stack.push(Self::entry(data, level, line.trim(), &caps[2], parent_book));
+            contexts.extend(Self::effective(&stack, start));
+        }
+
+        Self(contexts)
+    }
+
+    /// `# Jude 5` is Jude 1, see [`BibleData::parse`]
+    fn entry(
+        data: &BibleData,
+        level: usize,
+        heading: &str,
+        text: &str,
+        parent_book: Option<BookId>,
+    ) -> HeadingEntry {
+        let (book, chapter) = if let Some(psg) = data.parse(text) {
+            let chapter = psg.segments.first().map(|seg| seg.starting_chapter());
+            (Some(psg.book), chapter)
+        } else if let Some(book) = data.books().find_book(text) {
+            (Some(book), None)
+        } else if let Some(caps) = CHAPTER_HEADING.captures(text) {
+            (None, parent_book.and(caps[1].parse().ok()))
+        } else {
+            (None, None)
+        };
+        HeadingEntry {
+            level,
+            heading: heading.to_string(),
+            book,
+            chapter,
+        }
+    }
+
+    /// - The deepest heading naming a book decides the book
+    /// - A chapter only counts if it's at or below that heading
+    fn effective(stack: &[HeadingEntry], start: usize) -> Option<HeadingContext> {
+        let book_idx = stack.iter().rposition(|e| e.book.is_some())?;
+        let book = stack[book_idx].book?;
+        let chapter_idx = stack[book_idx..]
+            .iter()
+            .rposition(|e| e.chapter.is_some())
+            .map(|idx| book_idx + idx);
+
+        let heading_idx = chapter_idx.unwrap_or(book_idx);
+        Some(HeadingContext {
+            start,
+            heading: stack[heading_idx].heading.clone(),
+            book,
+            chapter: chapter_idx.and_then(|idx| stack[idx].chapter),
+        })
+    }
+
+    /// The context in effect at this byte, if any
+    pub fn at(&self, offset: usize) -> Option<&HeadingContext> {
+        let idx = self.0.partition_point(|ctx| ctx.start <= offset);
+        idx.checked_sub(1).map(|idx| &self.0[idx])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::{books::BookId, data::BibleData},
+        matcher::headings::HeadingContexts,
+    };
+
+    #[test]
+    fn scoped_headings() {
+        let input = "# Romans\n\nintro\n\n## Romans 8\n\nv. 28\n\n### Application\n\ntext\n\n# John 3\n\nv. 16\n";
+        let contexts = HeadingContexts::parse(&BibleData::default(), input);
+
+        let at = |needle: &str| {
+            let ctx = contexts.at(input.find(needle).unwrap()).unwrap();
+            (ctx.book, ctx.chapter, ctx.heading.clone())
+        };
+        assert_eq!(at("intro"), (BookId(45), None, "# Romans".to_string()));
+        assert_eq!(
+            at("v. 28"),
+            (BookId(45), Some(8), "## Romans 8".to_string())
+        );
+        assert_eq!(at("text"), (BookId(45), Some(8), "## Romans 8".to_string()));
+        assert_eq!(at("v. 16"), (BookId(43), Some(3), "# John 3".to_string()));
+
+        // a bare number in a book with one chapter is a verse
+        let input = "# Jude 5\n\nv. 6\n";
+        let contexts = HeadingContexts::parse(&BibleData::default(), input);
+        let ctx = contexts.at(input.find("v. 6").unwrap()).unwrap();
+        assert_eq!(ctx.chapter, Some(1));
+    }
+
+    #[test]
+    fn front_matter() {
+        let input = "---\ntitle: Notes\nbook: Philippians\n---\n\n## Chapter 2\n\nv. 5\n";
+        let contexts = HeadingContexts::parse(&BibleData::default(), input);
+
+        let ctx = contexts.at(input.find("v. 5").unwrap()).unwrap();
+        assert_eq!(ctx.book, BookId(50));
+        assert_eq!(ctx.chapter, Some(2));
+        assert_eq!(ctx.heading, "## Chapter 2");
+        assert!(contexts.at(0).is_none());
+    }
+}
@@ -1,11 +1,20 @@
 use line_col::LineColLookup;
-use regex::Match;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    data::{books::BookId, data::BibleData},
-    matcher::location::line_col::LineColLocation,
+    data::{
+        books::BookId, chapter_verses::BookChapterVerses, data::BibleData, detector::BookMatch,
+        versification::Versification,
+    },
+    matcher::{
+        citation::Citation,
+        confidence::Evidence,
+        location::line_col::LineColLocation,
+        options::{MatcherOptions, Strictness},
+    },
     segments::{
         parser::minimal::MinimalSegments,
+        passage_range::PassageRange,
         segments::{Passage, Segments},
     },
 };
@@ -76,6 +85,42 @@ pub struct BibleMatch<L = LineColLocation> {
     /// I want this to be of type [`Passage`] so that way I can use the
     /// [`Passage::overlaps_with`] function
     pub psg: Passage,
+    /// How the book (and chapter) of this match was determined
+    pub resolution: Resolution,
+    /// - How likely this is to really be a reference, from `0.0` to `1.0`
+    /// - See [`Evidence::score`] for what goes into it
+    pub confidence: f32,
+    /// - Set when this is a range into a later book (`Matthew 28 – Acts 1`)
+    /// - Then `psg` is only the part of it in the first book
+    pub range: Option<PassageRange>,
+    /// - Whether every chapter and verse of this match exists (`John 99:1` doesn't)
+    /// - See [`Strictness`] for what happens to the ones that don't
+    pub valid: bool,
+    /// Whether this is cited directly, or as a cross-reference (`cf.`), and where it is
+    pub citation: Citation,
+}
+
+/// Where the book of a match came from
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    /// The book name was written right before the reference
+    #[default]
+    Explicit,
+    /// A bare reference (`v. 16`, `4:1`, `ibid.`) attached to the most recently cited book
+    Inferred,
+    /// A bare reference under a heading (or front matter) that names the book
+    Heading(String),
+}
+
+impl std::fmt::Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resolution::Explicit => write!(f, "explicit"),
+            Resolution::Inferred => write!(f, "inferred"),
+            Resolution::Heading(heading) => write!(f, "heading: {heading}"),
+        }
+    }
 }
 
 impl<L> BibleMatch<L> {
@@ -83,25 +128,80 @@ impl<L> BibleMatch<L> {
         Self {
             location,
             psg: segments.with_book(book_id),
+            resolution: Resolution::Explicit,
+            confidence: 1.0,
+            range: None,
+            valid: true,
+            citation: Citation::default(),
         }
     }
+    pub fn with_resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence;
+        self
+    }
+    pub fn with_range(mut self, range: PassageRange) -> Self {
+        self.range = Some(range);
+        self
+    }
+    pub fn with_citation(mut self, citation: Citation) -> Self {
+        self.citation = citation;
+        self
+    }
+    pub fn with_valid(mut self, valid: bool) -> Self {
+        self.valid = valid;
+        self
+    }
+    pub fn is_inferred(&self) -> bool {
+        self.resolution != Resolution::Explicit
+    }
     pub fn map_loc<N>(self, f: impl FnOnce(L) -> N) -> BibleMatch<N> {
         BibleMatch {
             location: f(self.location),
             psg: self.psg,
+            resolution: self.resolution,
+            confidence: self.confidence,
+            range: self.range,
+            valid: self.valid,
+            citation: self.citation,
         }
     }
+    /// Whether every passage of this match (both ends of a range across books) exists
+    pub fn exists_in(&self, chapter_verses: &BookChapterVerses) -> bool {
+        chapter_verses.contains_passage(&self.psg)
+            && self.range.as_ref().is_none_or(|range| {
+                chapter_verses.contains_passage(&range.start)
+                    && chapter_verses.contains_passage(&range.end)
+            })
+    }
+    /// - Pulled back to the chapters and verses that exist, see [`BookChapterVerses::clamp`]
+    /// - [`None`] when any passage of it has nothing left
+    pub fn clamped(mut self, chapter_verses: &BookChapterVerses) -> Option<Self> {
+        self.psg = chapter_verses.clamp_passage(&self.psg)?;
+        if let Some(range) = self.range.take() {
+            self.range = Some(PassageRange {
+                start: chapter_verses.clamp_passage(&range.start)?,
+                end: chapter_verses.clamp_passage(&range.end)?,
+            });
+        }
+        self.valid = true;
+        Some(self)
+    }
 }
 
 impl BibleMatch {
     pub fn try_match<'a>(
         lookup: &LineColLookup,
         data: &'a BibleData,
+        options: &MatcherOptions,
         input: &str,
-        cur: Match<'a>,
+        cur: BookMatch<'a>,
         next_start: Option<usize>,
     ) -> Option<Self> {
-        let book_id = data.books().search(cur.as_str())?;
+        let book_id = cur.id;
 
         let mut segment_window = if let Some(next_start) = next_start {
             &input[cur.end()..next_start]
@@ -114,16 +214,65 @@ impl BibleMatch {
         //     segment_window = &segment_window[1..];
         // }
 
-        let segment_input = MinimalSegments::parse(segment_window)?;
+        let mut segment_input = MinimalSegments::parse(segment_window)?;
         // eprintln!("{} vs {}", old_segment_input.len(), segment_input.len());
 
+        if options.strictness != Strictness::Lenient {
+            segment_input =
+                longest_valid_prefix(data, options.versification, book_id, segment_input);
+        }
+
         let start = cur.start();
         let end = cur.end() + segment_input.len();
         let location = LineColLocation::new(&lookup, start, end);
 
+        let explicit_verse = segment_input
+            .segments
+            .iter()
+            .any(|seg| seg.explicit_start_verse.is_some());
+
         // let segments = Segments::parse(segment_input)?;
-        let segments = Segments::from(segment_input);
+        let segments = data.chapter_verses().read_segments(book_id, segment_input);
 
-        Some(BibleMatch::new(location, book_id, segments))
+        let psg = segments.with_book(book_id);
+        let confidence = Evidence {
+            book: cur.as_str(),
+            psg: &psg,
+            explicit_verse,
+            after: &input[end..],
+        }
+        .score(data);
+
+        Some(BibleMatch::new(location, psg.book, psg.segments).with_confidence(confidence))
+    }
+}
+
+/**
+- When not every segment exists, the most segments (from the start) that do
+- Ex: `Rom 8:28, 2019` is `Rom 8:28`, not `Rom 8:28, 201`
+- When not even the first one exists, it is kept as is (for [`Strictness`] to deal with)
+*/
+fn longest_valid_prefix(
+    data: &BibleData,
+    versification: Versification,
+    book_id: BookId,
+    segment_input: MinimalSegments,
+) -> MinimalSegments {
+    // the verse counts are in English numbering
+    let exists = |segments: &MinimalSegments| {
+        let psg = data
+            .chapter_verses()
+            .read_segments(book_id, segments.clone())
+            .with_book(book_id);
+        let psg = data.convert(&psg, versification, Versification::English);
+        data.chapter_verses().contains_passage(&psg)
+    };
+    if exists(&segment_input) {
+        return segment_input;
     }
+    (1..segment_input.segments.len())
+        .rev()
+        .map(|count| segment_input.prefix(count))
+        .find(|prefix| exists(prefix))
+        .unwrap_or(segment_input)
 }
@@ -0,0 +1,275 @@
+use line_col::LineColLookup;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{data::detector::BookDetector, matcher::location::line_col::LineColLocation};
+
+/**
+- A line break that might be in the middle of a reference, from hard-wrapped Markdown or PDF text
+- Only the part in the named group is replaced, `space` with a space, the rest with nothing:
+    - `1\nCorinthians`: the number of a book, and the name on the next line
+    - `Romans\n8:28`: a book name, and the chapter on the next line
+    - `Corin-\nthians`: a hyphenated book name (it must go on with a lowercase letter)
+    - `3:16-\n18`: a range, verse, or list split after its delimiter
+- The first three match plenty of prose too, so they are only joined when a book name (with a
+chapter after it) runs into the line break, see [`JoinedLines::joins_book`]
+*/
+static SPLIT_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?i:\b(?:[1-4]|i{1,3}|iv|first|second|third|fourth|1st|2nd|3rd|4th))(?P<space>[ \t]*\r?\n[ \t]*)\p{L}",
+        r"|\p{L}\.?(?P<space2>[ \t]*\r?\n[ \t]*)\d",
+        r"|\p{L}(?P<hyphen>-[ \t]*\r?\n[ \t]*)\p{Ll}",
+        r"|\d[ \t]*[-–—:,;][ \t]*(?P<delimiter>\r?\n[ \t]*)\d",
+    ))
+    .unwrap()
+});
+
+/// - A Markdown or Org heading, which is never joined to the line after it
+/// - Like the ones [`HeadingContexts`](crate::matcher::headings::HeadingContexts) reads
+static HEADING: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:#{1,6}|\*+)[ \t]").unwrap());
+
+/// A numbered list item (`2. `, `3) `), which is never joined to the line before it
+static LIST_ITEM: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[ \t]*\d+[.)][ \t]").unwrap());
+
+/// How much of the next line is needed to tell if a book name runs into the line break
+const NEXT_LINE_LEN: usize = 64;
+
+/// A run of the joined text, and where it came from
+#[derive(Copy, Clone, Debug)]
+struct Piece {
+    /// Where it starts in the joined text
+    joined: usize,
+    /// Where it starts in the original text
+    original: usize,
+    /// - How long it is in the original text
+    /// - A replaced line break is shorter (or longer) in the joined text
+    original_len: usize,
+}
+
+/**
+- The text with references that were split across lines joined back together (`Romans\n8:28` is
+`Romans 8:28`), so they can be matched
+- Every byte of the joined text maps back to the original text, so matches point at exactly what
+was written, even when they end on the next line
+- Headings and numbered list items are never joined to the line next to them (`# Romans` and
+`2 Corinthians 5` stay apart)
+- See [`MatcherOptions::join_lines`](crate::matcher::options::MatcherOptions::join_lines)
+*/
+#[derive(Clone, Debug)]
+pub struct JoinedLines {
+    text: String,
+    /// In order, and they cover all of `text`
+    pieces: Vec<Piece>,
+}
+
+impl JoinedLines {
+    /// - [`None`] when there is nothing to join
+    /// - `detector` has the book names references can start with
+    pub fn new(detector: &BookDetector, input: &str) -> Option<Self> {
+        let mut text = String::with_capacity(input.len());
+        let mut pieces = vec![];
+        let mut copied = 0;
+
+        for caps in SPLIT_REFERENCE.captures_iter(input) {
+            let (split, replacement, in_book) = match caps.name("space").or(caps.name("space2")) {
+                Some(space) => (space, " ", true),
+                None => match (caps.name("hyphen"), caps.name("delimiter")) {
+                    (Some(hyphen), _) => (hyphen, "", true),
+                    (None, Some(delimiter)) => (delimiter, "", false),
+                    (None, None) => continue,
+                },
+            };
+            if !Self::can_join(input, split.start(), split.end()) {
+                continue;
+            }
+            if in_book
+                && !Self::joins_book(detector, input, split.start(), split.end(), replacement)
+            {
+                continue;
+            }
+
+            pieces.push(Piece {
+                joined: text.len(),
+                original: copied,
+                original_len: split.start() - copied,
+            });
+            text.push_str(&input[copied..split.start()]);
+
+            pieces.push(Piece {
+                joined: text.len(),
+                original: split.start(),
+                original_len: split.len(),
+            });
+            text.push_str(replacement);
+            copied = split.end();
+        }
+        if pieces.is_empty() {
+            return None;
+        }
+
+        pieces.push(Piece {
+            joined: text.len(),
+            original: copied,
+            original_len: input.len() - copied,
+        });
+        text.push_str(&input[copied..]);
+        Some(Self { text, pieces })
+    }
+
+    /// Whether the lines on either side of the break (`input[start..end]`) may be joined at all
+    fn can_join(input: &str, start: usize, end: usize) -> bool {
+        let line_start = input[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line = &input[line_start..start];
+        !HEADING.is_match(line) && !LIST_ITEM.is_match(&input[end..])
+    }
+
+    /**
+    - Whether joining the break (`input[start..end]`) makes a book name with a chapter after it,
+    that the break is inside of (`1 Corinthians 13`) or right after (`Romans 8`)
+    - Only the line before the break and the start of the next one are looked at
+    */
+    fn joins_book(
+        detector: &BookDetector,
+        input: &str,
+        start: usize,
+        end: usize,
+        replacement: &str,
+    ) -> bool {
+        let line_start = input[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let next = &input[end..];
+        let mut next_end = next.find('\n').unwrap_or(next.len()).min(NEXT_LINE_LEN);
+        while !next.is_char_boundary(next_end) {
+            next_end -= 1;
+        }
+
+        let before = &input[line_start..start];
+        let joined = format!("{before}{replacement}{}", &next[..next_end]);
+        let at = before.len();
+        detector
+            .find_references(&joined)
+            .any(|book| book.start() < at && at <= book.end())
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Where the character at `idx` (in the joined text) starts in the original text
+    pub fn original_start(&self, idx: usize) -> usize {
+        let piece = self.pieces[self.pieces.partition_point(|p| p.joined <= idx) - 1];
+        piece.original + (idx - piece.joined).min(piece.original_len)
+    }
+
+    /// Where something that ends at `idx` (in the joined text) ends in the original text
+    pub fn original_end(&self, idx: usize) -> usize {
+        let count = self.pieces.partition_point(|p| p.joined < idx);
+        let Some(piece) = count.checked_sub(1).map(|i| self.pieces[i]) else {
+            return 0;
+        };
+        piece.original + (idx - piece.joined).min(piece.original_len)
+    }
+
+    /// A location in the joined text, as one in the original text (which `lookup` is of)
+    pub fn original_location(
+        &self,
+        lookup: &LineColLookup,
+        location: LineColLocation,
+    ) -> LineColLocation {
+        LineColLocation::new(
+            lookup,
+            self.original_start(location.bytes.start),
+            self.original_end(location.bytes.end),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::books::Books,
+        matcher::{
+            line_breaks::JoinedLines, location::line_col::LineColLocation, matcher::BibleMatcher,
+            options::MatcherOptions,
+        },
+    };
+
+    #[test]
+    fn join() {
+        let detector = Books::base().detector();
+        let input = "1\nCorinthians 13:4, Romans\n8:28, 2 Corin-\nthians 5 and John 3:16-\n  18";
+        let joined = JoinedLines::new(detector, input).unwrap();
+        assert_eq!(
+            joined.text(),
+            "1 Corinthians 13:4, Romans 8:28, 2 Corinthians 5 and John 3:16-18"
+        );
+        assert!(JoinedLines::new(detector, "Romans 8:28\nJohn 3:16").is_none());
+    }
+
+    #[test]
+    fn prose() {
+        let detector = Books::base().detector();
+        // a word before a number, a hyphenated word, and an ordinal that aren't in a book name
+        let input = "We met in room\n12 after the well-\nknown talk, part I\nof which was long";
+        assert!(JoinedLines::new(detector, input).is_none());
+
+        // a heading, and a numbered list after a book name
+        let input = "# Romans\n2 Corinthians 5:17 is next\nWe read Romans\n2. Then we prayed";
+        assert!(JoinedLines::new(detector, input).is_none());
+    }
+
+    /// (passage, what was matched, start, end)
+    fn found<'a>(
+        matcher: &BibleMatcher,
+        input: &'a str,
+    ) -> Vec<(String, &'a str, (usize, usize), (usize, usize))> {
+        matcher
+            .search::<LineColLocation>(input)
+            .unwrap()
+            .iter()
+            .map(|m| {
+                let loc = m.location;
+                (
+                    matcher.data().format_passage(&m.psg).unwrap(),
+                    &input[loc.bytes.start..loc.bytes.end],
+                    (loc.start.line, loc.start.column),
+                    (loc.end.line, loc.end.column),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn split_references() {
+        let input = [
+            "Love is patient (1",
+            "Corinthians 13:4). All things work together (Romans",
+            "8:28) for good, and see John 3:16-",
+            "18 too.",
+        ]
+        .join("\n");
+
+        let matcher = BibleMatcher::default();
+        let without = found(&matcher, &input);
+        assert!(!without.iter().any(|(psg, ..)| psg == "1 Corinthians 13:4"));
+
+        let matcher = matcher.with_options(MatcherOptions::default().with_join_lines());
+        assert_eq!(
+            found(&matcher, &input),
+            vec![
+                (
+                    "1 Corinthians 13:4".to_string(),
+                    "1\nCorinthians 13:4",
+                    (1, 18),
+                    (2, 17)
+                ),
+                ("Romans 8:28".to_string(), "Romans\n8:28", (2, 46), (3, 5)),
+                (
+                    "John 3:16-18".to_string(),
+                    "John 3:16-\n18",
+                    (3, 25),
+                    (4, 3)
+                ),
+            ]
+        );
+    }
+}
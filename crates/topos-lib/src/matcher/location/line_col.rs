@@ -1,9 +1,18 @@
 use line_col::LineColLookup;
-use regex::Match;
 
-use crate::matcher::{
-    instance::BibleMatch,
-    matcher::{BibleMatcher, MatchResult, Matcher},
+use crate::{
+    data::detector::BookMatch,
+    matcher::{
+        anaphora::resolve_bare_references,
+        citation::with_citations,
+        instance::BibleMatch,
+        line_breaks::JoinedLines,
+        matcher::{BibleMatcher, MatchResult, Matcher},
+        ranges::join_book_ranges,
+        spoken::with_spoken_matches,
+        versification::into_english,
+        whole_books::with_whole_book_matches,
+    },
 };
 
 #[derive(Copy, Clone, Debug)]
@@ -59,54 +68,173 @@ impl Matcher for LineColLocation {
         matcher: &BibleMatcher,
         input: Self::Input<'a>,
     ) -> MatchResult<Vec<BibleMatch<Self>>> {
-        let mut filtered = matcher.filter();
-
-        let mut prev: Option<Match<'_>> = None;
-        let lookup = LineColLookup::new(input);
-        // basically execute behind by 1 iteration (so I can see the start of the next match)
-        for cur in matcher.filtered_books.captures_iter(input) {
-            // this is just the book name
-            let cur = cur.get(1).unwrap();
-            if let Some(prev) = prev {
-                if let Some(m) = BibleMatch::try_match(
-                    &lookup,
-                    // self.data.as_ref(),
-                    matcher.data(),
-                    input,
-                    prev,
-                    Some(cur.start()),
-                ) {
-                    filtered.try_add(m);
-                }
+        if matcher.options().join_lines {
+            if let Some(joined) = JoinedLines::new(matcher.reference_detector(), input) {
+                let lookup = LineColLookup::new(input);
+                let matches = search_text(matcher, joined.text())
+                    .into_iter()
+                    .map(|m| m.map_loc(|loc| joined.original_location(&lookup, loc)))
+                    .collect();
+                return Ok(matches);
             }
-            prev = Some(cur);
         }
+        Ok(search_text(matcher, input))
+    }
+}
+
+/// Every match in `input`, with the whole pipeline (and the filter) applied
+fn search_text(matcher: &BibleMatcher, input: &str) -> Vec<BibleMatch<LineColLocation>> {
+    let lookup = LineColLookup::new(input);
+    let mut matches = explicit_matches(matcher, &lookup, input);
+    matches = join_book_ranges(&lookup, input, matches);
+
+    if matcher.options().spoken {
+        matches = with_spoken_matches(matcher, &lookup, input, matches);
+    }
+
+    if matcher.options().resolves_context() {
+        matches = resolve_bare_references(matcher, &lookup, input, matches);
+    }
+
+    // after resolving context, so a whole book is never what `v. 16` attaches to
+    if matcher.options().whole_books {
+        matches = with_whole_book_matches(matcher, &lookup, input, matches);
+    }
 
-        // handle last one
+    matches = with_citations(input, matches);
+    matches = into_english(matcher, matches);
+
+    let mut filtered = matcher.filter();
+    for m in matches {
+        filtered.try_add(m);
+    }
+    filtered.matches()
+}
+
+/// Every reference with a book name before it, before the complex filter is applied
+fn explicit_matches(
+    matcher: &BibleMatcher,
+    lookup: &LineColLookup,
+    input: &str,
+) -> Vec<BibleMatch<LineColLocation>> {
+    let mut matches = vec![];
+    let mut prev: Option<BookMatch<'_>> = None;
+    // basically execute behind by 1 iteration (so I can see the start of the next match)
+    for cur in matcher.reference_detector().find_references(input) {
         if let Some(prev) = prev {
-            if let Some(m) = BibleMatch::try_match(&lookup, matcher.data(), input, prev, None) {
-                filtered.try_add(m);
+            if let Some(m) = BibleMatch::try_match(
+                lookup,
+                matcher.data(),
+                matcher.options(),
+                input,
+                prev,
+                Some(cur.start()),
+            ) {
+                matches.push(m);
             }
         }
+        prev = Some(cur);
+    }
 
-        let matches = filtered.matches();
-        return Ok(matches);
+    // handle last one
+    if let Some(prev) = prev {
+        if let Some(m) =
+            BibleMatch::try_match(lookup, matcher.data(), matcher.options(), input, prev, None)
+        {
+            matches.push(m);
+        }
     }
 
-    fn find<'a>(matcher: &BibleMatcher, input: Self::Input<'a>) -> Option<BibleMatch<Self>> {
-        let mut filtered = matcher.filter();
-        let lookup = LineColLookup::new(input);
+    matches
+}
 
-        let first = matcher.filtered_books.captures_iter(input).next()?.get(1)?;
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::{data::BibleData, locale::Locale},
+        filter::filter::BibleFilter,
+        matcher::{
+            location::line_col::LineColLocation, matcher::BibleMatcher, options::MatcherOptions,
+        },
+    };
+
+    #[test]
+    fn unicode_spans() {
+        let matcher = BibleMatcher::default();
+        let input = "见 John ３：１６，以及 Romans\u{a0}8∶28 and Ps ١١٩:١٠٥. Also John 3¹⁶!";
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+
+        let found: Vec<(&str, String)> = matches
+            .iter()
+            .map(|m| {
+                let bytes = m.location.bytes;
+                (&input[bytes.start..bytes.end], m.psg.segments.to_string())
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("John ３：１６", "3:16".to_string()),
+                ("Romans\u{a0}8∶28", "8:28".to_string()),
+                ("Ps ١١٩:١٠٥", "119:105".to_string()),
+                ("John 3¹⁶", "3:16".to_string()),
+            ]
+        );
+    }
 
-        filtered.try_add(BibleMatch::try_match(
-            &lookup,
-            matcher.data(),
-            input,
-            first,
-            None,
-        )?);
+    #[test]
+    fn locales() {
+        let data = BibleData::default()
+            .with_locales(&[Locale::English, Locale::Spanish, Locale::Chinese])
+            .unwrap()
+            .with_display_locale(Locale::Spanish)
+            .unwrap();
+        let matcher = BibleFilter::new(data).create_matcher().unwrap();
+        let input = "Leímos Juan 3:16, then Romans 8:28, y en el culto chino 约翰福音3:16和罗8:28.";
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+
+        let data = matcher.data();
+        let found: Vec<(&str, String)> = matches
+            .iter()
+            .map(|m| {
+                let bytes = m.location.bytes;
+                (
+                    &input[bytes.start..bytes.end],
+                    data.format_passage(&m.psg).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Juan 3:16", "Juan 3:16".to_string()),
+                ("Romans 8:28", "Romanos 8:28".to_string()),
+                ("约翰福音3:16", "Juan 3:16".to_string()),
+                ("罗8:28", "Romanos 8:28".to_string()),
+            ]
+        );
+    }
 
-        return filtered.matches().into_iter().next();
+    #[test]
+    fn find() {
+        let matcher = BibleMatcher::default().with_options(
+            MatcherOptions::default()
+                .with_whole_books()
+                .with_join_lines(),
+        );
+        let find = |input: &str| {
+            let m = matcher.find::<LineColLocation>(input)?;
+            matcher.data().format_passage(&m.psg)
+        };
+        // the first match of a search, whatever found it
+        assert_eq!(
+            find("We read the book of Ruth, then John 3:16"),
+            Some("Ruth".to_string())
+        );
+        assert_eq!(
+            find("See 1 Corin-\nthians 13:4 and John 3:16"),
+            Some("1 Corinthians 13:4".to_string())
+        );
+        assert_eq!(find("Nothing to see here"), None);
     }
 }
@@ -5,6 +5,9 @@ pub mod html;
 
 pub mod srt;
 
+/// Searching a reader (or chunks of text) without reading all of it first
+pub mod stream;
+
 /// https://github.com/Govcraft/vtt
 pub mod vtt;
 
@@ -241,6 +241,7 @@ impl<'a> PDFTextPageMatcher<'a> {
     // }
     pub fn try_next_char(&mut self, ch: TextChar<'_>) -> bool {
         if let Some(c) = ch.char() {
+            self.skip_line_break();
             let current_char = self.current_char();
             // if current_char.is_none() {
             //     dbg!(&self);
@@ -353,6 +354,20 @@ impl<'a> PDFTextPageMatcher<'a> {
         // - 1
     }
 
+    /**
+    - A match can go on to the next line (see [`MatcherOptions::join_lines`](crate::matcher::options::MatcherOptions::join_lines)),
+    but the glyphs of a line don't include the line break, or the indent of the next line
+    - The rectangle of each line is already added by [`PDFTextPageMatcher::finish_line`]
+    */
+    pub fn skip_line_break(&mut self) {
+        if !matches!(self.current_char(), Some('\n' | '\r')) {
+            return;
+        }
+        while self.current_char().is_some_and(char::is_whitespace) {
+            self.char_idx += 1;
+        }
+    }
+
     pub fn current_char(&mut self) -> Option<char> {
         // self.chars?.next()
         self.current_str().chars().nth(self.char_idx)
@@ -0,0 +1,370 @@
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, Read},
+};
+
+use crate::matcher::{
+    instance::BibleMatch,
+    location::line_col::{LineColLocation, Position},
+    matcher::BibleMatcher,
+};
+
+/// How much text is searched at once
+const CHUNK_SIZE: usize = 64 * 1024;
+/// How much of the end of a chunk is searched again with the next one
+const CARRY_SIZE: usize = 4 * 1024;
+
+/// Where the text currently being searched starts in the whole input
+#[derive(Copy, Clone, Debug)]
+struct Offset {
+    byte: usize,
+    /// 1-based, like [`Position::line`]
+    line: usize,
+    /// - The bytes of its line that came before it
+    /// - Only not `0` when a chunk had to be cut in the middle of a very long line
+    column: usize,
+}
+
+impl Offset {
+    fn position(&self, pos: Position) -> Position {
+        let column = if pos.line == 1 {
+            pos.column + self.column
+        } else {
+            pos.column
+        };
+        Position::new(self.line + pos.line - 1, column)
+    }
+
+    fn location(&self, location: LineColLocation) -> LineColLocation {
+        let mut bytes = location.bytes;
+        bytes.start += self.byte;
+        bytes.end += self.byte;
+        LineColLocation {
+            start: self.position(location.start),
+            end: self.position(location.end),
+            bytes,
+        }
+    }
+
+    /// Moves past `text`
+    fn advance(&mut self, text: &str) {
+        self.byte += text.len();
+        match text.rfind('\n') {
+            Some(idx) => {
+                self.line += text.matches('\n').count();
+                self.column = text.len() - idx - 1;
+            }
+            None => self.column += text.len(),
+        }
+    }
+}
+
+/**
+- Every match in a reader, found a chunk at a time, so the whole input is never in memory
+- Locations (lines, columns, and bytes) are of the whole input, not the chunk
+- The end of each chunk is searched again with the next one, so a reference split between them is
+still found (once)
+- Chunks are cut at the start of a line when they can be, so a reference (and the `cf.` before
+it) is only split when it is on a line longer than a chunk
+- Bare references (see [`MatcherOptions::anaphora`](crate::matcher::options::MatcherOptions::anaphora))
+and headings are only resolved within the chunk they are in
+- Create one with [`BibleMatcher::search_reader`] or [`BibleMatcher::search_chunks`]
+*/
+pub struct StreamMatches<'m, R> {
+    matcher: &'m BibleMatcher,
+    reader: R,
+    /// The text that has been read but not searched past yet
+    buffer: String,
+    /// Bytes of a character that was split between reads
+    partial: Vec<u8>,
+    offset: Offset,
+    /// Matches found in the last chunk that haven't been returned yet
+    pending: VecDeque<BibleMatch>,
+    /// Where the last returned match ends, so the carry-over doesn't match part of it again
+    emitted_end: usize,
+    chunk_size: usize,
+    carry_size: usize,
+    done: bool,
+}
+
+impl<'m, R: BufRead> StreamMatches<'m, R> {
+    pub fn new(matcher: &'m BibleMatcher, reader: R) -> Self {
+        Self {
+            matcher,
+            reader,
+            buffer: String::new(),
+            partial: vec![],
+            offset: Offset {
+                byte: 0,
+                line: 1,
+                column: 0,
+            },
+            pending: VecDeque::new(),
+            emitted_end: 0,
+            chunk_size: CHUNK_SIZE,
+            carry_size: CARRY_SIZE,
+            done: false,
+        }
+    }
+
+    /**
+    - How much text is searched at once (`64 KiB` by default)
+    - The end of each chunk (a quarter of it, at most `4 KiB`) is searched again with the next
+    one, so a single reference can't be longer than that
+    */
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(64);
+        self.carry_size = (self.chunk_size / 4).min(CARRY_SIZE);
+        self
+    }
+
+    /// - Reads until there is a whole chunk in the buffer
+    /// - Returns whether the reader is done
+    fn fill(&mut self) -> io::Result<bool> {
+        while self.buffer.len() < self.chunk_size {
+            let data = self.reader.fill_buf()?;
+            if data.is_empty() {
+                if !self.partial.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "stream did not end with valid UTF-8",
+                    ));
+                }
+                return Ok(true);
+            }
+            let take = data.len().min(self.chunk_size - self.buffer.len() + 4);
+            self.partial.extend_from_slice(&data[..take]);
+            self.reader.consume(take);
+
+            let valid = match std::str::from_utf8(&self.partial) {
+                Ok(text) => text.len(),
+                // an incomplete character at the end will be finished by the next read
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            };
+            let text = std::str::from_utf8(&self.partial[..valid]).expect("checked above");
+            self.buffer.push_str(text);
+            self.partial.drain(..valid);
+        }
+        Ok(false)
+    }
+
+    /**
+    - Where to stop returning matches from this chunk, leaving the rest to be searched again
+    - The start of a line if there is one near the carry-over, else after a space
+    */
+    fn cut(&self) -> usize {
+        let mut target = self.buffer.len().saturating_sub(self.carry_size);
+        while !self.buffer.is_char_boundary(target) {
+            target -= 1;
+        }
+        let mut lower = target.saturating_sub(self.carry_size);
+        while !self.buffer.is_char_boundary(lower) {
+            lower -= 1;
+        }
+        let near = &self.buffer[lower..target];
+        let cut = near
+            .rfind('\n')
+            .or_else(|| near.rfind(char::is_whitespace))
+            .map(|idx| lower + idx + near[idx..].chars().next().map_or(1, char::len_utf8));
+        cut.filter(|cut| *cut > 0).unwrap_or(target.max(1))
+    }
+
+    /// Searches the next chunk, and queues the matches that start before where it is cut
+    fn search_chunk(&mut self) -> io::Result<()> {
+        let eof = self.fill()?;
+        let cut = if eof { self.buffer.len() } else { self.cut() };
+
+        let matches = self
+            .matcher
+            .search::<LineColLocation>(self.buffer.as_str())
+            .unwrap_or_default();
+        for mut m in matches {
+            let start = m.location.bytes.start + self.offset.byte;
+            if m.location.bytes.start < cut && start >= self.emitted_end {
+                m.location = self.offset.location(m.location);
+                self.emitted_end = m.location.bytes.end;
+                self.pending.push_back(m);
+            }
+        }
+
+        self.offset.advance(&self.buffer[..cut]);
+        self.buffer.drain(..cut);
+        self.done = eof;
+        Ok(())
+    }
+}
+
+impl<'m, R: BufRead> Iterator for StreamMatches<'m, R> {
+    type Item = io::Result<BibleMatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(m) = self.pending.pop_front() {
+                return Some(Ok(m));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.search_chunk() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Reads from an iterator of chunks (`String`s, `&str`s, byte buffers), like they were one input
+pub struct ChunkReader<I: Iterator> {
+    chunks: I,
+    current: Option<I::Item>,
+    pos: usize,
+}
+
+impl<I: Iterator> ChunkReader<I>
+where
+    I::Item: AsRef<[u8]>,
+{
+    pub fn new(chunks: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            chunks: chunks.into_iter(),
+            current: None,
+            pos: 0,
+        }
+    }
+}
+
+impl<I: Iterator> Read for ChunkReader<I>
+where
+    I::Item: AsRef<[u8]>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let data = self.fill_buf()?;
+        let len = data.len().min(buf.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<I: Iterator> BufRead for ChunkReader<I>
+where
+    I::Item: AsRef<[u8]>,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self
+            .current
+            .as_ref()
+            .is_none_or(|chunk| self.pos >= chunk.as_ref().len())
+        {
+            match self.chunks.next() {
+                Some(chunk) => {
+                    self.current = Some(chunk);
+                    self.pos = 0;
+                }
+                None => return Ok(&[]),
+            }
+        }
+        let chunk = self.current.as_ref().expect("filled above");
+        Ok(&chunk.as_ref()[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+impl BibleMatcher {
+    /// Every match in `reader`, see [`StreamMatches`]
+    pub fn search_reader<R: BufRead>(&self, reader: R) -> StreamMatches<'_, R> {
+        StreamMatches::new(self, reader)
+    }
+
+    /// Every match in `chunks`, like they were one input, see [`StreamMatches`]
+    pub fn search_chunks<I>(&self, chunks: I) -> StreamMatches<'_, ChunkReader<I::IntoIter>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        StreamMatches::new(self, ChunkReader::new(chunks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::matcher::{location::line_col::LineColLocation, matcher::BibleMatcher};
+
+    /// (line, column, start byte, passage)
+    fn summary(
+        matcher: &BibleMatcher,
+        matches: impl Iterator<Item = super::BibleMatch>,
+    ) -> Vec<(usize, usize, usize, String)> {
+        matches
+            .map(|m| {
+                let passage = matcher.data().format_passage(&m.psg).unwrap();
+                let start = m.location.start;
+                (start.line, start.column, m.location.bytes.start, passage)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stream() {
+        let line = "Some filler text, then Romans 8:28 and John 3:16-18, ending with Gen 1:1.\n";
+        let input = line.repeat(200) + "Last one is Rev 22:21";
+        let matcher = BibleMatcher::default();
+
+        let whole = matcher.search::<LineColLocation>(input.as_str()).unwrap();
+        let expected = summary(&matcher, whole.into_iter());
+        assert_eq!(expected.len(), 601);
+
+        // small chunks, so plenty of references and lines are split between them
+        for chunk_size in [128, 200, 1000] {
+            let reader = BufReader::with_capacity(7, input.as_bytes());
+            let streamed = matcher
+                .search_reader(reader)
+                .with_chunk_size(chunk_size)
+                .map(Result::unwrap);
+            assert_eq!(summary(&matcher, streamed), expected);
+        }
+
+        // chunks that cut through the middle of references
+        let chunks: Vec<&str> = input
+            .as_bytes()
+            .chunks(13)
+            .map(|c| std::str::from_utf8(c).unwrap())
+            .collect();
+        let streamed = matcher
+            .search_chunks(chunks)
+            .with_chunk_size(256)
+            .map(Result::unwrap);
+        assert_eq!(summary(&matcher, streamed), expected);
+    }
+
+    #[test]
+    fn stream_long_line() {
+        // one line, so chunks are cut after spaces, and columns keep counting
+        let input = "é Romans 8:28, ".repeat(100);
+        let matcher = BibleMatcher::default();
+
+        let whole = matcher.search::<LineColLocation>(input.as_str()).unwrap();
+        let expected = summary(&matcher, whole.into_iter());
+        assert_eq!(expected.len(), 100);
+
+        let streamed = matcher
+            .search_chunks(input.as_bytes().chunks(5))
+            .with_chunk_size(128)
+            .map(Result::unwrap);
+        assert_eq!(summary(&matcher, streamed), expected);
+    }
+
+    #[test]
+    fn stream_invalid_utf8() {
+        let matcher = BibleMatcher::default();
+        let bytes: &[u8] = b"John 3:16 \xff";
+        let results: Vec<_> = matcher.search_chunks([bytes]).collect();
+        assert!(results.last().is_some_and(|r| r.is_err()));
+    }
+}
@@ -1,10 +1,9 @@
 use std::marker::PhantomData;
 
 use line_col::LineColLookup;
-use regex::{Match, Regex};
 
 use crate::{
-    data::data::BibleData,
+    data::{data::BibleData, detector::BookDetector},
     filter::filter::BibleFilter,
     matcher::{
         instance::BibleMatch,
@@ -12,6 +11,7 @@ use crate::{
             html::HTMLMatchError, line_col::LineColLocation, pdf::PDFMatchError, srt::SRTMatchError,
         },
         matches::{ComplexFilter, FilteredBibleMatches},
+        options::MatcherOptions,
     },
     segments::autocomplete::input::InputAutoCompleter,
 };
@@ -19,28 +19,72 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct BibleMatcher {
     data: BibleData,
-    /// The books to **not** match on **aren't** in this RegEx, so I won't process unnecessary books
-    pub filtered_books: Regex,
+    /// The books to **not** match on **aren't** in this detector, so I won't process unnecessary books
+    pub filtered_books: BookDetector,
     /// These are so I can check if the matches overlap with these
     complex_filter: ComplexFilter,
+    options: MatcherOptions,
 }
 
 // TODO: I should have a search method for each type of Location
 impl BibleMatcher {
-    pub fn new(data: BibleData, filtered_books: Regex, complex_filter: ComplexFilter) -> Self {
+    pub fn new(
+        data: BibleData,
+        filtered_books: BookDetector,
+        complex_filter: ComplexFilter,
+    ) -> Self {
         Self {
             data,
             filtered_books,
             complex_filter,
+            options: MatcherOptions::default(),
         }
     }
 
+    pub fn with_options(mut self, options: MatcherOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     pub fn data(&self) -> &BibleData {
         &self.data
     }
 
+    pub fn options(&self) -> &MatcherOptions {
+        &self.options
+    }
+
+    /// - The detector for finding explicit references
+    /// - When resolving context, this has every book (the filter drops the extra matches later)
+    pub fn reference_detector(&self) -> &BookDetector {
+        if self.options.resolves_context() {
+            self.data.books().detector()
+        } else {
+            &self.filtered_books
+        }
+    }
+
+    /// - Every book name, even without a number after it, for spoken references
+    /// - Only when spoken references are enabled
+    pub fn spoken_books(&self) -> Option<&BookDetector> {
+        self.options.spoken.then(|| self.data.books().detector())
+    }
+
+    /// - The full name of every book, for whole-book references
+    /// - Only when whole-book references are enabled
+    pub fn book_names(&self) -> Option<&BookDetector> {
+        self.options
+            .whole_books
+            .then(|| self.data.books().name_detector())
+    }
+
     pub fn filter(&self) -> FilteredBibleMatches<'_> {
-        self.complex_filter.as_filter()
+        self.complex_filter
+            .as_filter()
+            .with_min_confidence(self.options.min_confidence)
+            .with_strictness(self.options.strictness)
+            .with_roles(self.options.roles.as_ref())
+            .with_chapter_verses(self.data.chapter_verses())
     }
 
     pub fn completer(&self) -> InputAutoCompleter {
@@ -1,9 +1,18 @@
-use crate::{matcher::instance::BibleMatch, segments::segments::Passage};
+use std::collections::BTreeSet;
+
+use crate::{
+    data::{books::BookId, chapter_verses::BookChapterVerses},
+    matcher::{citation::Role, instance::BibleMatch, options::Strictness},
+    segments::segments::Passage,
+};
 
 #[derive(Clone, Debug, Default)]
 pub struct ComplexFilter {
     inside_of: Vec<Passage>,
     outside_of: Vec<Passage>,
+    /// - The books regex already skips other books, but inferred matches never go through it
+    /// - [`None`] means every book is allowed
+    books: Option<BTreeSet<BookId>>,
 }
 
 impl ComplexFilter {
@@ -11,9 +20,14 @@ impl ComplexFilter {
         Self {
             inside_of,
             outside_of,
+            books: None,
         }
     }
 
+    pub fn restrict_books(&mut self, books: BTreeSet<BookId>) {
+        self.books = Some(books);
+    }
+
     pub fn inside(&mut self, psg: Passage) {
         self.inside_of.push(psg);
     }
@@ -23,6 +37,14 @@ impl ComplexFilter {
     }
 
     pub fn keep(&self, psg: &Passage) -> bool {
+        if self
+            .books
+            .as_ref()
+            .is_some_and(|books| !books.contains(&psg.book))
+        {
+            return false;
+        }
+
         let is_inside = self.inside_of.is_empty()
             || self
                 .inside_of
@@ -48,6 +70,14 @@ impl ComplexFilter {
 
 pub struct FilteredBibleMatches<'a> {
     filter: &'a ComplexFilter,
+    /// See [`MatcherOptions::min_confidence`](crate::matcher::options::MatcherOptions::min_confidence)
+    min_confidence: Option<f32>,
+    /// See [`MatcherOptions::roles`](crate::matcher::options::MatcherOptions::roles)
+    roles: Option<&'a BTreeSet<Role>>,
+    /// See [`MatcherOptions::strictness`](crate::matcher::options::MatcherOptions::strictness)
+    strictness: Strictness,
+    /// Needed to split ranges that cross books, see [`PassageRange::passages`](crate::segments::passage_range::PassageRange::passages)
+    chapter_verses: Option<&'a BookChapterVerses>,
     matches: Vec<BibleMatch>,
 }
 
@@ -55,12 +85,70 @@ impl<'a> FilteredBibleMatches<'a> {
     pub fn new(filter: &'a ComplexFilter) -> Self {
         Self {
             filter,
+            min_confidence: None,
+            strictness: Strictness::default(),
+            roles: None,
+            chapter_verses: None,
             matches: vec![],
         }
     }
 
-    pub fn try_add(&mut self, m: BibleMatch) {
-        if self.filter.keep(&m.psg) {
+    pub fn with_min_confidence(mut self, min_confidence: Option<f32>) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    pub fn with_roles(mut self, roles: Option<&'a BTreeSet<Role>>) -> Self {
+        self.roles = roles;
+        self
+    }
+
+    pub fn with_chapter_verses(mut self, chapter_verses: &'a BookChapterVerses) -> Self {
+        self.chapter_verses = Some(chapter_verses);
+        self
+    }
+
+    /// - A range across books is kept if any book it covers is
+    /// - Without chapter verses, every match is taken to be [`valid`](BibleMatch::valid)
+    pub fn try_add(&mut self, mut m: BibleMatch) {
+        if self
+            .min_confidence
+            .is_some_and(|min_confidence| m.confidence < min_confidence)
+        {
+            return;
+        }
+        if self
+            .roles
+            .is_some_and(|roles| !roles.contains(&m.citation.role))
+        {
+            return;
+        }
+        if let Some(chapter_verses) = self.chapter_verses {
+            m.valid = m.exists_in(chapter_verses);
+            if !m.valid {
+                m = match self.strictness {
+                    Strictness::Lenient | Strictness::Flag => m,
+                    Strictness::Clamp => match m.clamped(chapter_verses) {
+                        Some(m) => m,
+                        None => return,
+                    },
+                    Strictness::Drop => return,
+                };
+            }
+        }
+        let keep = match (&m.range, self.chapter_verses) {
+            (Some(range), Some(chapter_verses)) => range
+                .passages(chapter_verses)
+                .iter()
+                .any(|psg| self.filter.keep(psg)),
+            _ => self.filter.keep(&m.psg),
+        };
+        if keep {
             self.matches.push(m);
         }
     }
@@ -69,3 +157,55 @@ impl<'a> FilteredBibleMatches<'a> {
         self.matches
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::{
+        location::line_col::LineColLocation,
+        matcher::BibleMatcher,
+        options::{MatcherOptions, Strictness},
+    };
+
+    #[test]
+    fn strictness() {
+        let input = "John 99:1 and Jude 5:2, then John 3:16-99. Rom 8:28, 2019 was a good year.";
+        let search = |strictness: Strictness| {
+            let matcher = BibleMatcher::default()
+                .with_options(MatcherOptions::default().with_strictness(strictness));
+            matcher.search::<LineColLocation>(input).unwrap()
+        };
+        let found = |strictness: Strictness| -> Vec<(String, bool)> {
+            search(strictness)
+                .iter()
+                .map(|m| (format!("{} {}", *m.psg.book, m.psg.segments), m.valid))
+                .collect()
+        };
+
+        // the year is read as verse 201
+        let lenient = search(Strictness::Lenient);
+        assert_eq!(lenient.len(), 4);
+        assert!(lenient.iter().all(|m| !m.valid));
+        assert_eq!(lenient[3].psg.segments.len(), 2);
+
+        let flagged = search(Strictness::Flag);
+        let romans = &flagged[3].location.bytes;
+        assert_eq!(&input[romans.start..romans.end], "Rom 8:28");
+        assert_eq!(
+            found(Strictness::Flag),
+            vec![
+                ("43 99:1".to_string(), false),
+                ("65 5:2".to_string(), false),
+                ("43 3:16-99".to_string(), false),
+                ("45 8:28".to_string(), true),
+            ]
+        );
+        assert_eq!(
+            found(Strictness::Clamp),
+            vec![
+                ("43 3:16-36".to_string(), true),
+                ("45 8:28".to_string(), true)
+            ]
+        );
+        assert_eq!(found(Strictness::Drop), vec![("45 8:28".to_string(), true)]);
+    }
+}
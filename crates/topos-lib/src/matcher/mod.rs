@@ -1,4 +1,14 @@
+pub mod anaphora;
+pub mod citation;
+pub mod confidence;
+pub mod headings;
 pub mod instance;
+pub mod line_breaks;
 pub mod location;
 pub mod matcher;
 pub mod matches;
+pub mod options;
+pub mod ranges;
+pub mod spoken;
+pub mod versification;
+pub mod whole_books;
@@ -0,0 +1,154 @@
+use std::{collections::BTreeSet, fmt::Display, str::FromStr};
+
+use crate::{data::versification::Versification, matcher::citation::Role};
+
+/**
+- Opt-in behaviors for a [`BibleMatcher`](crate::matcher::matcher::BibleMatcher)
+- Everything is off by default, so the default matcher only finds references with a book name
+*/
+#[derive(Clone, Debug, Default)]
+pub struct MatcherOptions {
+    /// Resolve bare references (`v. 16`, `vv. 18-20`, `4:1`, `ibid.`) to the most recently cited book
+    pub anaphora: Option<AnaphoraOptions>,
+    /// Resolve bare references to the book (and chapter) named by the Markdown/Org heading or
+    /// front matter `book:` they are under
+    pub headings: bool,
+    /// Also find references that were said out loud (`John chapter three verse sixteen`), like in
+    /// transcripts
+    pub spoken: bool,
+    /// Also find books named without a chapter (`the book of Ruth`), as the whole book
+    pub whole_books: bool,
+    /// - Drop matches whose [`confidence`](crate::matcher::instance::BibleMatch::confidence) is
+    /// below this
+    /// - [`None`] keeps every match
+    pub min_confidence: Option<f32>,
+    /// - How the text being searched numbers its verses
+    /// - Matches are renumbered into [`Versification::English`], like the book data
+    pub versification: Versification,
+    /// What to do with references to chapters or verses that don't exist (`John 99:1`)
+    pub strictness: Strictness,
+    /// - Only keep matches cited this way (`cf.` references are [`Role::CrossReference`])
+    /// - [`None`] keeps every match
+    pub roles: Option<BTreeSet<Role>>,
+    /// - Join references split across lines (`Romans\n8:28`, `1 Corin-\nthians 13`) before matching,
+    /// like in PDFs and hard-wrapped Markdown
+    /// - Locations are still of the original text, see [`JoinedLines`](crate::matcher::line_breaks::JoinedLines)
+    pub join_lines: bool,
+}
+
+impl MatcherOptions {
+    pub fn with_anaphora(mut self, anaphora: AnaphoraOptions) -> Self {
+        self.anaphora = Some(anaphora);
+        self
+    }
+    pub fn with_headings(mut self) -> Self {
+        self.headings = true;
+        self
+    }
+    pub fn with_spoken(mut self) -> Self {
+        self.spoken = true;
+        self
+    }
+    pub fn with_whole_books(mut self) -> Self {
+        self.whole_books = true;
+        self
+    }
+    pub fn with_min_confidence(mut self, min_confidence: f32) -> Self {
+        self.min_confidence = Some(min_confidence);
+        self
+    }
+    pub fn with_versification(mut self, versification: Versification) -> Self {
+        self.versification = versification;
+        self
+    }
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+    pub fn with_roles(mut self, roles: impl IntoIterator<Item = Role>) -> Self {
+        self.roles = Some(roles.into_iter().collect());
+        self
+    }
+    pub fn with_join_lines(mut self) -> Self {
+        self.join_lines = true;
+        self
+    }
+    /// Whether bare references get resolved at all
+    pub fn resolves_context(&self) -> bool {
+        self.anaphora.is_some() || self.headings
+    }
+}
+
+/**
+- What to do with a reference whose chapter or verse doesn't exist in its book (`John 99:1`,
+`Jude 5:2`), using the verse counts in [`BookChapterVerses`](crate::data::chapter_verses::BookChapterVerses)
+- Every match says whether it exists in [`valid`](crate::matcher::instance::BibleMatch::valid)
+- Everything but [`Strictness::Lenient`] first falls back to the longest list of segments that
+does exist, so `Rom 8:28, 2019` doesn't take `201` as a verse
+*/
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Keep every reference as it was written
+    #[default]
+    Lenient,
+    /// Keep invalid references, but they are marked as not [`valid`](crate::matcher::instance::BibleMatch::valid)
+    Flag,
+    /// - Pull ends back to the last chapter/verse that exists (`John 3:16-99` is `John 3:16-36`)
+    /// - References that start somewhere that doesn't exist are dropped
+    Clamp,
+    /// Drop invalid references
+    Drop,
+}
+
+impl Strictness {
+    pub const ALL: [Strictness; 4] = [
+        Strictness::Lenient,
+        Strictness::Flag,
+        Strictness::Clamp,
+        Strictness::Drop,
+    ];
+}
+
+impl Display for Strictness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Strictness::Lenient => write!(f, "lenient"),
+            Strictness::Flag => write!(f, "flag"),
+            Strictness::Clamp => write!(f, "clamp"),
+            Strictness::Drop => write!(f, "drop"),
+        }
+    }
+}
+
+impl FromStr for Strictness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "lenient" | "off" | "none" => Strictness::Lenient,
+            "flag" | "mark" => Strictness::Flag,
+            "clamp" => Strictness::Clamp,
+            "drop" | "strict" => Strictness::Drop,
+            _ => Err(format!("Unknown strictness `{s}`"))?,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct AnaphoraOptions {
+    /// - How far (in bytes) a bare reference may be from the end of the previous reference
+    /// - Past this, the previous book is considered out of scope and the bare reference is ignored
+    pub window: usize,
+}
+
+impl AnaphoraOptions {
+    pub fn new(window: usize) -> Self {
+        Self { window }
+    }
+}
+
+impl Default for AnaphoraOptions {
+    fn default() -> Self {
+        Self { window: 500 }
+    }
+}
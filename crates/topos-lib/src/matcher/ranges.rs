@@ -0,0 +1,96 @@
+use line_col::LineColLookup;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    matcher::{
+        instance::{BibleMatch, Resolution},
+        location::line_col::LineColLocation,
+    },
+    segments::passage_range::PassageRange,
+};
+
+/// Nothing but a dash between two references
+static RANGE_GAP: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*[-–—―⸺]\s*$").unwrap());
+
+/**
+- Joins two explicit references with only a dash between them into one range, when the second is
+in a later book (`Matthew 28 – Acts 1`)
+- The joined match keeps the first book's part as its passage, and is only as confident as the less
+confident of the two
+- `matches` must be in order of where they start, and so is the output
+*/
+pub fn join_book_ranges(
+    lookup: &LineColLookup,
+    input: &str,
+    matches: Vec<BibleMatch>,
+) -> Vec<BibleMatch> {
+    let mut joined: Vec<BibleMatch> = Vec::with_capacity(matches.len());
+    for m in matches {
+        let Some(prev) = joined.last() else {
+            joined.push(m);
+            continue;
+        };
+        let (start, gap_start, gap_end) = (
+            prev.location.bytes.start,
+            prev.location.bytes.end,
+            m.location.bytes.start,
+        );
+        let joinable = prev.range.is_none()
+            && prev.resolution == Resolution::Explicit
+            && m.resolution == Resolution::Explicit
+            && gap_start <= gap_end
+            && RANGE_GAP.is_match(&input[gap_start..gap_end]);
+        let range = joinable
+            .then(|| PassageRange::new(prev.psg.clone(), m.psg.clone()))
+            .flatten();
+        let Some(range) = range else {
+            joined.push(m);
+            continue;
+        };
+
+        let confidence = prev.confidence.min(m.confidence);
+        let location = LineColLocation::new(lookup, start, m.location.bytes.end);
+        let psg = prev.psg.clone();
+        joined.pop();
+        joined.push(
+            BibleMatch::new(location, psg.book, psg.segments)
+                .with_confidence(confidence)
+                .with_range(range),
+        );
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::{location::line_col::LineColLocation, matcher::BibleMatcher};
+
+    #[test]
+    fn book_ranges() {
+        let matcher = BibleMatcher::default();
+        let input = "This week: Gen 50:26–Ex 1:7, then Matthew 28 - Acts 1 and Romans 1-2.";
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+
+        let data = matcher.data();
+        let found: Vec<String> = matches
+            .iter()
+            .filter_map(|m| match &m.range {
+                Some(range) => data.format_range(range),
+                None => data.format_passage(&m.psg),
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                "Genesis 50:26 – Exodus 1:7",
+                "Matthew 28 – Acts 1",
+                "Romans 1-2",
+            ]
+        );
+        assert_eq!(
+            &input[matches[1].location.bytes.start..matches[1].location.bytes.end],
+            "Matthew 28 - Acts 1"
+        );
+    }
+}
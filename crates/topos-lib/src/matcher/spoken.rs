@@ -0,0 +1,65 @@
+use line_col::LineColLookup;
+
+use crate::{
+    matcher::{
+        confidence::Evidence, instance::BibleMatch, location::line_col::LineColLocation,
+        matcher::BibleMatcher,
+    },
+    segments::{segment::Segment, spoken::SpokenSegments},
+};
+
+/**
+- Adds the references that were said out loud (`John chapter three verse sixteen`) to the written
+ones
+- Spoken references that overlap a written one are skipped, since the written one is more precise
+- Spoken matches get a [`confidence`](BibleMatch::confidence) the same way written ones do
+- `written` must be in order of where they start, and so is the output
+*/
+pub fn with_spoken_matches(
+    matcher: &BibleMatcher,
+    lookup: &LineColLookup,
+    input: &str,
+    written: Vec<BibleMatch>,
+) -> Vec<BibleMatch> {
+    let Some(books) = matcher.spoken_books() else {
+        return written;
+    };
+
+    let mut spoken = vec![];
+    for book in books.find_books(input) {
+        let book_id = book.id;
+        let after = book.end();
+        let Some(parsed) = SpokenSegments::parse(&input[after..]) else {
+            continue;
+        };
+        let end = after + parsed.len();
+        let overlaps = written
+            .iter()
+            .any(|m| m.location.bytes.start < end && book.start() < m.location.bytes.end);
+        if overlaps {
+            continue;
+        }
+
+        // `chapter three verse sixteen` is as good as a colon
+        let explicit_verse = parsed
+            .segments
+            .iter()
+            .any(|seg| !matches!(seg, Segment::FullChapter(_) | Segment::FullChapterRange(_)));
+        let psg = parsed.segments.with_book(book_id);
+        let confidence = Evidence {
+            book: book.name(),
+            psg: &psg,
+            explicit_verse,
+            after: &input[end..],
+        }
+        .score(matcher.data());
+
+        let location = LineColLocation::new(lookup, book.start(), end);
+        spoken.push(BibleMatch::new(location, psg.book, psg.segments).with_confidence(confidence));
+    }
+
+    let mut all = written;
+    all.extend(spoken);
+    all.sort_by_key(|m| m.location.bytes.start);
+    all
+}
@@ -0,0 +1,108 @@
+use crate::{
+    data::versification::Versification,
+    matcher::{
+        confidence::revalidate,
+        instance::{BibleMatch, Resolution},
+        matcher::BibleMatcher,
+    },
+    segments::segments::Passage,
+};
+
+/**
+- Renumbers every match from the versification of the text into
+[`Versification::English`], so they can be filtered and grouped with everything else
+- Whether the verses exist is checked again, since `Psalm 51:21` only exists before it's
+renumbered (as `Psalm 51:19`)
+- A reference to only a Psalm title (`Psalm 3:1` in the Masoretic text) is dropped, since there
+is no English verse for it
+*/
+pub fn into_english<L>(matcher: &BibleMatcher, matches: Vec<BibleMatch<L>>) -> Vec<BibleMatch<L>> {
+    let from = matcher.options().versification;
+    if from == Versification::English {
+        return matches;
+    }
+    let data = matcher.data();
+    let to = Versification::English;
+
+    matches
+        .into_iter()
+        .filter_map(|mut m| {
+            let psg = data.convert(&m.psg, from, to);
+            let range = m.range.take().map(|range| {
+                data.versifications()
+                    .convert_range(&range, from, to, data.chapter_verses())
+            });
+            let empty = |psg: &Passage| psg.segments.is_empty();
+            if empty(&psg)
+                || range
+                    .as_ref()
+                    .is_some_and(|r| empty(&r.start) || empty(&r.end))
+            {
+                return None;
+            }
+            // bare references take the confidence of what they were resolved from
+            if m.resolution == Resolution::Explicit {
+                m.confidence = revalidate(m.confidence, &m.psg, &psg, data);
+            }
+            m.psg = psg;
+            m.range = range;
+            Some(m)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::versification::Versification,
+        matcher::{
+            location::line_col::LineColLocation, matcher::BibleMatcher, options::MatcherOptions,
+        },
+    };
+
+    #[test]
+    fn source_versification() {
+        let input = "Psalm 51:21 and Mal 3:23, but also John 3:16";
+        let search = |versification: Versification| {
+            let matcher = BibleMatcher::default()
+                .with_options(MatcherOptions::default().with_versification(versification));
+            let matches = matcher.search::<LineColLocation>(input).unwrap();
+            matches
+                .iter()
+                .map(|m| {
+                    let passage = matcher.data().format_passage(&m.psg).unwrap();
+                    (passage, (m.confidence * 100.0).round() / 100.0)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            search(Versification::English),
+            vec![
+                ("Psalms 51:21".to_string(), 0.5),
+                ("Malachi 3:23".to_string(), 0.5),
+                ("John 3:16".to_string(), 1.0),
+            ]
+        );
+        assert_eq!(
+            search(Versification::Masoretic),
+            vec![
+                ("Psalms 51:19".to_string(), 1.0),
+                ("Malachi 4:5".to_string(), 1.0),
+                ("John 3:16".to_string(), 1.0),
+            ]
+        );
+
+        // a Psalm title has no English verse
+        let matcher = BibleMatcher::default()
+            .with_options(MatcherOptions::default().with_versification(Versification::Masoretic));
+        let matches = matcher
+            .search::<LineColLocation>("Psalm 3:1 and Psalm 3:2")
+            .unwrap();
+        let passages: Vec<String> = matches
+            .iter()
+            .filter_map(|m| matcher.data().format_passage(&m.psg))
+            .collect();
+        assert_eq!(passages, vec!["Psalms 3:1"]);
+    }
+}
@@ -0,0 +1,133 @@
+use line_col::LineColLookup;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    matcher::{
+        confidence::Evidence, instance::BibleMatch, location::line_col::LineColLocation,
+        matcher::BibleMatcher,
+    },
+    segments::segment::Segment,
+};
+
+/// `John 3` is a chapter (even if it didn't parse), not the whole book
+static CHAPTER_AFTER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\.?\s*\d").unwrap());
+
+/// `the book of`, `the letter to the`, `the Gospel of`, `read through`, right before the name
+static BOOK_CUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:(?:books?|epistles?|letters?|gospel|prophecy)\s+(?:of|to)\s+(?:the\s+)?|(?:read(?:ing)?|stud(?:y|ied|ying)|finish(?:ed)?|start(?:ed)?)\s+(?:through\s+)?)$",
+    )
+    .unwrap()
+});
+
+/// `in Jude we see`: `in` right before the name, and what it says right after it
+static IN_CUE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bin\s+$").unwrap());
+static SAYS_CUE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^,?\s+(?:we|you|it)\s+(?:see|read|learn|find|says)\b").unwrap());
+
+/**
+- Adds the books that were named without a chapter (`the book of Ruth`, `in Jude we see`) to the
+other matches, as the whole book
+- Only full names count, and names that overlap another match (or have a chapter after them) are
+skipped
+- A name is often just a person (`John said`, `Mark brought snacks`), so only names with something
+that says they're a book (`the book of`, `read through`, `in ... we see`) count
+- `matches` must be in order of where they start, and so is the output
+*/
+pub fn with_whole_book_matches(
+    matcher: &BibleMatcher,
+    lookup: &LineColLookup,
+    input: &str,
+    matches: Vec<BibleMatch>,
+) -> Vec<BibleMatch> {
+    let Some(names) = matcher.book_names() else {
+        return matches;
+    };
+
+    let mut whole = vec![];
+    for name in names.find_books(input) {
+        // the capitalization has to match too (`Job`, not `job`)
+        let Some(book_id) = matcher.data().books().search_name(name.name()) else {
+            continue;
+        };
+        // a period after it ends the sentence, it isn't an abbreviation
+        let (start, end) = (name.start(), name.start() + name.name().len());
+        if CHAPTER_AFTER.is_match(&input[end..]) {
+            continue;
+        }
+        let cued = BOOK_CUE.is_match(&input[..start])
+            || (IN_CUE.is_match(&input[..start]) && SAYS_CUE.is_match(&input[end..]));
+        if !cued {
+            continue;
+        }
+        let overlaps = matches
+            .iter()
+            .any(|m| m.location.bytes.start < end && start < m.location.bytes.end);
+        if overlaps {
+            continue;
+        }
+
+        let psg = Segment::full_book().as_segments().with_book(book_id);
+        let confidence = Evidence {
+            book: name.name(),
+            psg: &psg,
+            explicit_verse: false,
+            after: &input[end..],
+        }
+        .score(matcher.data());
+
+        let location = LineColLocation::new(lookup, start, end);
+        whole.push(BibleMatch::new(location, psg.book, psg.segments).with_confidence(confidence));
+    }
+
+    let mut all = matches;
+    all.extend(whole);
+    all.sort_by_key(|m| m.location.bytes.start);
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::{
+        location::line_col::LineColLocation, matcher::BibleMatcher, options::MatcherOptions,
+    };
+
+    #[test]
+    fn whole_books() {
+        let input = "We read the book of Ruth, and in Jude we see Romans 8:28 again.";
+
+        let matches = BibleMatcher::default()
+            .search::<LineColLocation>(input)
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+
+        let matcher =
+            BibleMatcher::default().with_options(MatcherOptions::default().with_whole_books());
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+        let found: Vec<(String, f32)> = matches
+            .iter()
+            .map(|m| {
+                let passage = matcher.data().format_passage(&m.psg).unwrap();
+                (passage, (m.confidence * 100.0).round() / 100.0)
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Ruth".to_string(), 0.8),
+                ("Jude".to_string(), 0.8),
+                ("Romans 8:28".to_string(), 1.0),
+            ]
+        );
+
+        // a name with nothing saying it's a book is just a name
+        let input = "Mark brought snacks, John said grace, and we finished reading through Ruth.";
+        let matches = matcher.search::<LineColLocation>(input).unwrap();
+        let found: Vec<&str> = matches
+            .iter()
+            .map(|m| &input[m.location.bytes.start..m.location.bytes.end])
+            .collect();
+        assert_eq!(found, vec!["Ruth"]);
+    }
+}
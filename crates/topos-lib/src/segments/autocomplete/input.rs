@@ -1,6 +1,5 @@
 use constcat::concat;
 use once_cell::sync::Lazy;
-use regex::Regex;
 
 use crate::{
     data::chapter_verses::BookChapterVerses,
@@ -15,24 +14,25 @@ use crate::{
 
 pub struct InputAutoCompleter<'a> {
     matcher: &'a BibleMatcher,
-    book_regex: Regex,
     // bcv: BookChapterVerses
 }
 
 impl<'a> InputAutoCompleter<'a> {
+    /// This is cheap: the book names are found with the detector the books already have
     pub fn new(matcher: &'a BibleMatcher) -> Self {
-        let book_regex = matcher.data().create_book_regex().unwrap();
-        Self {
-            matcher,
-            book_regex,
-        }
+        Self { matcher }
     }
 
     /// - This assumes your cursor is at the end of the input
     pub fn suggest(&self, input: &str) -> Option<CompletionOutput> {
-        let cap = self.book_regex.captures_iter(input).last()?;
-        let book_match = cap.get(1).unwrap();
-        let book_id = self.matcher.data().books().search(book_match.as_str())?;
+        let book_match = self
+            .matcher
+            .data()
+            .books()
+            .detector()
+            .find_books(input)
+            .last()?;
+        let book_id = book_match.id;
 
         let segments_input = &input[book_match.end()..];
         let (mat, full_segments) = parse_full_segments(segments_input)?;
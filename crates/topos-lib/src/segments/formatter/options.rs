@@ -36,8 +36,26 @@ pub struct RangeOptions {
 pub struct FormatOptions {
     /// have spacing for numbers and each type of delimeter that overwrite this
     pub general_spacing: SpaceOptions,
+    /// `3:23a` instead of `3:23`
     pub include_subverse: bool,
     pub roman: RomanNumeralOptions,
     pub delim: DelimeterOptions,
     pub range: RangeOptions,
 }
+
+/// Leave everything as it was, and keep subverses
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            general_spacing: SpaceOptions::DontTouch,
+            include_subverse: true,
+            roman: RomanNumeralOptions::DontTouch,
+            delim: DelimeterOptions::DontTouch,
+            range: RangeOptions {
+                exclude_verse_1_for_chapter_range: false,
+                join_adjacent_verses: false,
+                use_chapter_in_single_chapter_books: false,
+            },
+        }
+    }
+}
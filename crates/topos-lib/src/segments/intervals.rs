@@ -0,0 +1,101 @@
+use crate::{data::chapter_verses::VerseOrdinal, segments::units::range_pair::RangePair};
+
+/**
+- A static interval index over inclusive verse ordinal ranges
+- Entries are sorted by their start, alongside a running maximum of their ends, so a query is a
+binary search followed by a backwards scan that stops as soon as nothing earlier can reach the query
+- It is built once and then only queried, which is all an on-disk reference index needs
+*/
+#[derive(Clone, Debug)]
+pub struct IntervalIndex<T> {
+    entries: Vec<(RangePair<VerseOrdinal>, T)>,
+    /// `max_end[i]` is the largest end of `entries[..=i]`
+    max_end: Vec<VerseOrdinal>,
+}
+
+impl<T> IntervalIndex<T> {
+    pub fn new(mut entries: Vec<(RangePair<VerseOrdinal>, T)>) -> Self {
+        entries.sort_by_key(|(range, _)| (range.start, range.end));
+        let max_end = entries
+            .iter()
+            .scan(0, |max, (range, _)| {
+                *max = (*max).max(range.end);
+                Some(*max)
+            })
+            .collect();
+        Self { entries, max_end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every entry overlapping the query, in order of where they start
+    pub fn overlapping(&self, query: RangePair<VerseOrdinal>) -> Vec<&T> {
+        // everything after this starts after the query ends
+        let upper = self
+            .entries
+            .partition_point(|(range, _)| range.start <= query.end);
+
+        let mut found = vec![];
+        for idx in (0..upper).rev() {
+            if self.max_end[idx] < query.start {
+                break;
+            }
+            let (range, value) = &self.entries[idx];
+            if range.end >= query.start {
+                found.push(value);
+            }
+        }
+        found.reverse();
+        found
+    }
+}
+
+impl<T> Default for IntervalIndex<T> {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+impl<T> FromIterator<(RangePair<VerseOrdinal>, T)> for IntervalIndex<T> {
+    fn from_iter<I: IntoIterator<Item = (RangePair<VerseOrdinal>, T)>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{intervals::IntervalIndex, units::range_pair::RangePair};
+
+    #[test]
+    fn overlapping() {
+        let index: IntervalIndex<&str> = vec![
+            (RangePair::new(10, 20), "a"),
+            (RangePair::new(0, 100), "b"),
+            (RangePair::new(15, 15), "c"),
+            (RangePair::new(30, 40), "d"),
+            (RangePair::new(21, 29), "e"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            index.overlapping(RangePair::new(15, 15)),
+            vec![&"b", &"a", &"c"]
+        );
+        assert_eq!(
+            index.overlapping(RangePair::new(20, 21)),
+            vec![&"b", &"a", &"e"]
+        );
+        assert_eq!(index.overlapping(RangePair::new(41, 50)), vec![&"b"]);
+        assert_eq!(
+            index.overlapping(RangePair::new(101, 200)),
+            Vec::<&&str>::new()
+        );
+    }
+}
@@ -1,8 +1,11 @@
 pub mod autocomplete;
 pub mod formatter;
+pub mod intervals;
 pub mod parse;
 pub mod parser;
+pub mod passage_range;
 pub mod segment;
 pub mod segments;
+pub mod spoken;
 pub mod units;
 pub mod verse_bounds;
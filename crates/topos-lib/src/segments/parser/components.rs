@@ -1,18 +1,63 @@
 use std::u8;
 
-use chumsky::prelude::*;
+use chumsky::{prelude::*, text::inline_whitespace};
 
 pub struct Decimal;
 impl Decimal {
+    /// - Digits from any script in [`Decimal::digit_value`] (`16`, `１６`, `١٦`, `१६`)
+    /// - Superscripts are left out, since they're verses attached to a chapter (`3¹⁶`), see
+    /// [`Decimal::superscript_parser`]
     pub fn parser<'a>() -> impl Parser<'a, &'a str, u8> {
+        Self::digits(Self::digit_value)
+    }
+
+    /// `¹⁶` in `3¹⁶`
+    pub fn superscript_parser<'a>() -> impl Parser<'a, &'a str, u8> {
+        Self::digits(Self::superscript_value)
+    }
+
+    fn digits<'a>(value: fn(char) -> Option<u8>) -> impl Parser<'a, &'a str, u8> {
         any()
-            .filter(|c: &char| c.is_numeric())
+            .try_map(move |c: char, _| value(c).ok_or(EmptyErr::default()))
             .repeated()
             .at_least(1)
             .at_most(3)
-            .to_slice()
-            .from_str()
-            .try_map(|v, span| v.map_err(|_| EmptyErr::default()))
+            .collect::<Vec<u8>>()
+            .try_map(|digits, _| {
+                digits
+                    .iter()
+                    .try_fold(0u8, |acc, d| acc.checked_mul(10)?.checked_add(*d))
+                    .ok_or(EmptyErr::default())
+            })
+    }
+
+    /**
+    - The value of a decimal digit: ASCII, full-width (CJK), Arabic-Indic, Extended Arabic-Indic
+    (Persian/Urdu), or Devanagari
+    - [`char::is_numeric`] is too loose, it takes things like `½` that aren't digits at all
+    */
+    pub fn digit_value(c: char) -> Option<u8> {
+        let zero = match c {
+            '0'..='9' => '0',
+            '０'..='９' => '０',
+            '٠'..='٩' => '٠',
+            '۰'..='۹' => '۰',
+            '०'..='९' => '०',
+            _ => return None,
+        };
+        Some((c as u32 - zero as u32) as u8)
+    }
+
+    /// The value of a superscript digit (`¹`, `²`, and `³` aren't next to the others)
+    pub fn superscript_value(c: char) -> Option<u8> {
+        match c {
+            '⁰' => Some(0),
+            '¹' => Some(1),
+            '²' => Some(2),
+            '³' => Some(3),
+            '⁴'..='⁹' => Some((c as u32 - '⁰' as u32) as u8),
+            _ => None,
+        }
     }
 }
 
@@ -25,6 +70,36 @@ impl Subverse {
     pub fn optional_parser<'a>() -> impl Parser<'a, &'a str, Option<char>> {
         Self::parser().or_not()
     }
+    pub fn is_subverse(c: char) -> bool {
+        Self::SUBVERSE.contains(c)
+    }
+}
+
+/// The `f.`/`ff.` after a verse
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Onward {
+    /// `f.`: the verse and the one after it
+    NextVerse,
+    /// `ff.`: the verse through the end of the chapter
+    Chapter,
+}
+
+impl Onward {
+    /// - `28f`, `28f.`, `28ff`, `28ff.`, or `28 ff.` (a space needs the period, `28 f` could be anything)
+    /// - It can't be the start of a word, like `28for`
+    pub fn parser<'a>() -> impl Parser<'a, &'a str, Self> {
+        let suffix = just("ff")
+            .to(Self::Chapter)
+            .or(just("f").to(Self::NextVerse));
+        let attached = suffix.clone().then_ignore(just('.').or_not());
+        let spaced = inline_whitespace()
+            .at_least(1)
+            .ignore_then(suffix)
+            .then_ignore(just('.'));
+        attached
+            .or(spaced)
+            .then_ignore(any().filter(|c: &char| c.is_alphanumeric()).not())
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -35,24 +110,29 @@ pub enum Delimeter {
 }
 
 impl Delimeter {
-    /// `,` or `;`
-    const SEGMENT_DELIMETER: &'static str = r",;";
-    /// `,` or `;`
+    /// `,` or `;` (or their full-width forms)
+    const SEGMENT_DELIMETER: &'static str = r",;，；";
+    /// `,` or `;` (or their full-width forms)
     pub fn segment_parser<'a>() -> impl Parser<'a, &'a str, char> {
         one_of(Self::SEGMENT_DELIMETER)
     }
 
-    /// `.` or `:`
-    const CHAPTER_DELIMETER: &'static str = r".:";
-    /// `.` or `:`
+    /// `.`, `:`, the full-width colon `：`, or the ratio sign `∶`
+    const CHAPTER_DELIMETER: &'static str = r".:：∶";
+    /// `.`, `:`, the full-width colon `：`, or the ratio sign `∶`
     pub fn chapter_parser<'a>() -> impl Parser<'a, &'a str, char> {
         one_of(Self::CHAPTER_DELIMETER)
     }
 
     /// Various dashes
-    const RANGE_DELIMETER: &str = r"-–——⸺";
+    const RANGE_DELIMETER: &str = r"-–——⸺－";
     /// Various dashes
     pub fn range_parser<'a>() -> impl Parser<'a, &'a str, char> {
         one_of(Self::RANGE_DELIMETER)
     }
+
+    /// Whether this is one of the dashes [`Delimeter::range_parser`] takes
+    pub fn is_range(c: char) -> bool {
+        Self::RANGE_DELIMETER.contains(c)
+    }
 }
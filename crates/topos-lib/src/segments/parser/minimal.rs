@@ -3,6 +3,15 @@ use crate::segments::parser::roman_numeral::RomanNumerals;
 use chumsky::prelude::*;
 use chumsky::text::whitespace;
 
+/// A chapter or verse number, and the subverse (`16a`) if it had one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinimalNumber {
+    pub value: u8,
+    /// - Only meaningful on verses
+    /// - Ex: `a` in `16a`
+    pub subverse: Option<char>,
+}
+
 /// This is 1/4th the size of the others, for searching I should parse this, but for auto-complete
 /// I should use the others
 /// `\d+(:\d+)?(-\d+(:\d+)?)?`
@@ -10,13 +19,20 @@ use chumsky::text::whitespace;
 pub struct MinimalSegment {
     /// `\d+(:\d+)?(-\d+(:\d+)?)?`
     /// `\d+`
-    pub start: u8,
+    pub start: MinimalNumber,
     /// `\d+(:\d+)?(-\d+(:\d+)?)?`
     /// -->`(:\d+)?`
-    pub explicit_start_verse: Option<u8>,
+    pub explicit_start_verse: Option<MinimalNumber>,
     /// `\d+(:\d+)?(-\d+(:\d+)?)?`
     /// --------->`(-\d+(:\d+)?)?`
-    pub end: Option<(u8, Option<u8>)>,
+    pub end: Option<(MinimalNumber, Option<MinimalNumber>)>,
+    /// `\d+:\d+ff.`
+    /// --------->`ff.`
+    /// - Only used when there is no `end`
+    pub onward: Option<Onward>,
+    /// - Where this segment is in the input, including its leading whitespace
+    /// - Not including the delimeter before it
+    pub span: SimpleSpan,
 }
 
 /// Call [`MinimalSegments::parse`], which will match all segments (at least 1) and return the span
@@ -34,13 +50,32 @@ impl MinimalSegments {
     pub fn len(&self) -> usize {
         self.span.end - self.span.start
     }
+    /// - Only the first `count` segments (at least 1), ending where the last of them ends
+    /// - Ex: `8:28` from `8:28, 201` (a year cut off at 3 digits)
+    pub fn prefix(&self, count: usize) -> Self {
+        let count = count.clamp(1, self.segments.len());
+        let segments = self.segments[..count].to_vec();
+        let end = segments.last().map_or(self.span.end, |seg| seg.span.end);
+        Self {
+            segments,
+            span: SimpleSpan::from(self.span.start..end),
+        }
+    }
 }
 
 /// Only take leading whitespace
-fn only_numbers<'a>() -> impl Parser<'a, &'a str, u8> {
+fn only_numbers<'a>() -> impl Parser<'a, &'a str, MinimalNumber> {
     whitespace()
         .ignore_then(Decimal::parser().or(RomanNumerals::parser()))
-        .then_ignore(Subverse::optional_parser())
+        .then(Subverse::optional_parser())
+        .map(|(value, subverse)| MinimalNumber { value, subverse })
+}
+
+/// A verse written as a superscript right after its chapter (`¹⁶` in `3¹⁶`)
+fn superscript_verse<'a>() -> impl Parser<'a, &'a str, MinimalNumber> {
+    Decimal::superscript_parser()
+        .then(Subverse::optional_parser())
+        .map(|(value, subverse)| MinimalNumber { value, subverse })
 }
 
 /// WARNING: This will not tolerate trailing white-space, this is to be handled by the segment
@@ -53,8 +88,10 @@ fn minimal_full_segment_parser<'a>() -> impl Parser<'a, &'a str, MinimalSegment>
         .then(
             whitespace()
                 .ignore_then(Delimeter::chapter_parser().ignore_then(only_numbers()))
+                .or(superscript_verse())
                 .or_not(),
         )
+        // `f.`/`ff.` only go on a single number (`8:28ff`, `Rom 8ff`), not the end of a range
         .then(
             whitespace()
                 .ignore_then(
@@ -64,13 +101,18 @@ fn minimal_full_segment_parser<'a>() -> impl Parser<'a, &'a str, MinimalSegment>
                             .or_not(),
                     ),
                 )
-                .or_not(),
+                .map(|end| (Some(end), None))
+                .or(Onward::parser().or_not().map(|onward| (None, onward))),
+        )
+        .map_with(
+            |((start, explicit_start_verse), (end, onward)), e| MinimalSegment {
+                start,
+                explicit_start_verse,
+                end,
+                onward,
+                span: e.span(),
+            },
         )
-        .map(|((start, explicit_start_verse), end)| MinimalSegment {
-            start,
-            explicit_start_verse,
-            end,
-        })
 }
 
 fn minimal_full_segments_parser<'a>() -> impl Parser<'a, &'a str, MinimalSegments> {
@@ -139,6 +181,72 @@ mod tests {
         _ = dbg!(p("hi ok but yes 1 : 1a- 2:   3  , 4:5-7 this ends here"));
     }
 
+    #[test]
+    fn test_subverse() {
+        let p = |input: &str| minimal_full_segment_parser().parse(input).into_result();
+        let seg = p("3:23a-24b").unwrap();
+        assert_eq!(seg.start.subverse, None);
+        assert_eq!(seg.explicit_start_verse.unwrap().subverse, Some('a'));
+        assert_eq!(seg.end.unwrap().0.subverse, Some('b'));
+    }
+
+    #[test]
+    fn test_unicode_digits() {
+        let p = |input: &str| {
+            minimal_full_segments_parser()
+                .parse(input)
+                .into_output()
+                .map(|v| {
+                    let seg = &v.segments[0];
+                    let verse = seg.explicit_start_verse.map(|v| v.value);
+                    (seg.start.value, verse, v.len())
+                })
+        };
+        assert_eq!(p("３：１６"), Some((3, Some(16), "３：１６".len())));
+        assert_eq!(p("3∶16"), Some((3, Some(16), "3∶16".len())));
+        assert_eq!(p("٣:١٦"), Some((3, Some(16), "٣:١٦".len())));
+        assert_eq!(p("३:१६"), Some((3, Some(16), "३:१६".len())));
+        assert_eq!(p("3¹⁶ says"), Some((3, Some(16), "3¹⁶".len())));
+        assert_eq!(p("\u{a0}3\u{2009}:\u{2009}16"), Some((3, Some(16), 12)));
+        // `½` is numeric, but it isn't a digit
+        assert_eq!(p("½"), None);
+    }
+
+    #[test]
+    fn test_onward() {
+        let p = |input: &str| {
+            minimal_full_segments_parser()
+                .parse(input)
+                .into_output()
+                .map(|v| (v.segments[0].onward, v.len()))
+        };
+        assert_eq!(p("8:28ff"), Some((Some(Onward::Chapter), 6)));
+        assert_eq!(p("8:28ff. and"), Some((Some(Onward::Chapter), 7)));
+        assert_eq!(p("12:1f."), Some((Some(Onward::NextVerse), 6)));
+        assert_eq!(p("12:1 f."), Some((Some(Onward::NextVerse), 7)));
+        assert_eq!(p("12:1 f"), Some((None, 4)));
+        assert_eq!(p("12:1for"), Some((None, 4)));
+        assert_eq!(p("8ff"), Some((Some(Onward::Chapter), 3)));
+        // the end of a range is already where it ends
+        assert_eq!(p("8:28-30ff"), Some((None, 7)));
+        assert_eq!(p("8-9f."), Some((None, 3)));
+    }
+
+    #[test]
+    fn test_prefix() {
+        let p = |input: &str, count: usize| {
+            minimal_full_segments_parser()
+                .parse(input)
+                .into_output()
+                .map(|v| v.prefix(count))
+                .map(|v| (v.segments.len(), v.len()))
+        };
+        assert_eq!(p("8:28, 201", 1), Some((1, 4)));
+        assert_eq!(p("8:28, 201", 2), Some((2, 9)));
+        assert_eq!(p(" 1:1 ; 2 ; 3", 2), Some((2, 8)));
+        assert_eq!(p("1:1, 2", 0), Some((1, 3)));
+    }
+
     #[test]
     fn test_len() {
         let p = |input: &str, len: usize| {
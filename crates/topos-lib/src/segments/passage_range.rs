@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::chapter_verses::{BookChapterVerses, VerseOrdinal},
+    segments::{
+        segment::Segment,
+        segments::{Passage, Segments},
+        units::range_pair::RangePair,
+        verse_bounds::VerseBounds,
+    },
+};
+
+/**
+- A range of verses that spans books, in canonical order (`Genesis 50:26 – Exodus 1:7`)
+- Only where `start` starts and where `end` ends matter
+- Use [`PassageRange::passages`] to split it into one [`Passage`] per book
+*/
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PassageRange {
+    pub start: Passage,
+    pub end: Passage,
+}
+
+impl PassageRange {
+    /// [`None`] unless `end` is in a later book than `start`, and both have a segment
+    pub fn new(start: Passage, end: Passage) -> Option<Self> {
+        let valid = start.book < end.book && !start.segments.is_empty() && !end.segments.is_empty();
+        valid.then_some(Self { start, end })
+    }
+
+    /**
+    - One passage per book: the rest of the first book, every book in between (whole), and the
+    last book up to where the range ends
+    - `Genesis 50:26 – Exodus 1:7` => `Genesis 50:26ff.`, `Exodus 1:1-7`
+    */
+    pub fn passages(&self, chapter_verses: &BookChapterVerses) -> Vec<Passage> {
+        let mut passages = vec![self.head(chapter_verses)];
+        passages.extend(
+            chapter_verses
+                .books_between(self.start.book, self.end.book)
+                .map(|book| Segment::full_book().as_segments().with_book(book)),
+        );
+        passages.push(self.tail());
+        passages
+    }
+
+    /// From where the range starts to the end of the first book
+    fn head(&self, chapter_verses: &BookChapterVerses) -> Passage {
+        let (chapter, verse) = self
+            .start
+            .segments
+            .first()
+            .map(|seg| (seg.starting_chapter(), seg.starting_verse()))
+            .unwrap_or((1, 1));
+        let last_chapter = chapter_verses
+            .get_chapter_verses(&self.start.book)
+            .map(|cv| cv.get_chapter_count())
+            .unwrap_or(chapter);
+
+        let mut segments = Segments::new();
+        let rest_start = if verse == 1 {
+            chapter
+        } else {
+            segments.push(Segment::chapter_verse_onward(chapter, verse));
+            chapter.saturating_add(1)
+        };
+        if chapter == 1 && verse == 1 {
+            segments.push(Segment::full_book());
+        } else if rest_start < last_chapter {
+            segments.push(Segment::full_chapter_range(rest_start, last_chapter));
+        } else if rest_start == last_chapter {
+            segments.push(Segment::full_chapter(rest_start));
+        }
+        segments.with_book(self.start.book)
+    }
+
+    /// From the start of the last book to where the range ends
+    fn tail(&self) -> Passage {
+        let last = self.end.segments.last();
+        let seg = match last {
+            // the end is a whole book itself
+            Some(Segment::FullBook(_)) | None => Segment::full_book(),
+            Some(seg) => match (seg.ending_chapter(), seg.ending_verse()) {
+                (1, None) => Segment::full_chapter(1),
+                (chapter, None) => Segment::full_chapter_range(1, chapter),
+                (chapter, Some(verse)) => Segment::chapter_range(1, 1, chapter, verse)
+                    .with_subverses(None, seg.ending_subverse()),
+            },
+        };
+        seg.as_segments().with_book(self.end.book)
+    }
+
+    /// - The (inclusive) ordinal range this covers
+    /// - [`None`] if either end isn't in `chapter_verses`
+    pub fn ordinals(&self, chapter_verses: &BookChapterVerses) -> Option<RangePair<VerseOrdinal>> {
+        let start = chapter_verses
+            .passage_ordinals(&self.head(chapter_verses))
+            .first()?
+            .start;
+        let end = chapter_verses.passage_ordinals(&self.tail()).last()?.end;
+        Some(RangePair::new(start, end))
+    }
+
+    pub fn overlaps_with(&self, chapter_verses: &BookChapterVerses, other: &Passage) -> bool {
+        let Some(range) = self.ordinals(chapter_verses) else {
+            return false;
+        };
+        chapter_verses
+            .passage_ordinals(other)
+            .iter()
+            .any(|o| o.start <= range.end && range.start <= o.end)
+    }
+
+    /// Every segment of `other` is inside this range
+    pub fn contains(&self, chapter_verses: &BookChapterVerses, other: &Passage) -> bool {
+        let Some(range) = self.ordinals(chapter_verses) else {
+            return false;
+        };
+        let ordinals = chapter_verses.passage_ordinals(other);
+        ordinals.len() == other.segments.len()
+            && ordinals
+                .iter()
+                .all(|o| range.start <= o.start && o.end <= range.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::{books::Books, chapter_verses::BookChapterVerses, data::BibleData},
+        error::AnyResult,
+    };
+
+    #[test]
+    fn passage_range() -> AnyResult<()> {
+        let books = Books::base();
+        let data = BibleData::default();
+        let chapter_verses = BookChapterVerses::default();
+        let range = |s: &str| books.parse_range(s).ok_or(format!("Failed to parse {s}"));
+        let psg = |s: &str| books.parse(s).ok_or(format!("Failed to parse {s}"));
+
+        let gen_ex = range("Gen 50:26–Ex 1:7")?;
+        assert_eq!(
+            data.format_range(&gen_ex).as_deref(),
+            Some("Genesis 50:26 – Exodus 1:7")
+        );
+        let split: Vec<String> = gen_ex
+            .passages(&chapter_verses)
+            .iter()
+            .filter_map(|p| data.format_passage(p))
+            .collect();
+        assert_eq!(split, vec!["Genesis 50:26ff.", "Exodus 1:1-7"]);
+
+        let mt_acts = range("Matthew 28 - Acts 1")?;
+        let split: Vec<String> = mt_acts
+            .passages(&chapter_verses)
+            .iter()
+            .filter_map(|p| data.format_passage(p))
+            .collect();
+        assert_eq!(split, vec!["Matthew 28", "Mark", "Luke", "John", "Acts 1"]);
+
+        assert!(mt_acts.overlaps_with(&chapter_verses, &psg("John 3:16")?));
+        assert!(mt_acts.overlaps_with(&chapter_verses, &psg("Matthew 27:66-28:1")?));
+        assert!(!mt_acts.overlaps_with(&chapter_verses, &psg("Acts 2:1")?));
+        assert!(mt_acts.contains(&chapter_verses, &psg("Luke 1-3")?));
+        assert!(!mt_acts.contains(&chapter_verses, &psg("Matthew 27:66-28:1")?));
+
+        let json = serde_json::to_string(&gen_ex)?;
+        assert_eq!(serde_json::from_str::<super::PassageRange>(&json)?, gen_ex);
+
+        // not a cross-book range
+        assert!(books.parse_range("Genesis 1-2").is_none());
+        assert!(books.parse_range("Exodus 1 - Genesis 2").is_none());
+
+        Ok(())
+    }
+}
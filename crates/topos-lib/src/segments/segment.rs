@@ -1,14 +1,22 @@
 use serde::{Deserialize, Serialize};
-use std::fmt::{Debug, Display};
-
-use crate::segments::{
-    segments::Segments,
-    units::{
-        chapter_range::ChapterRange, chapter_verse::ChapterVerse,
-        chapter_verse_range::ChapterVerseRange, full_chapter::FullChapter,
-        full_chapter_range::FullChapterRange, full_chapter_verse_range::FullChapterVerseRange,
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+};
+
+use crate::{
+    data::chapter_verses::ChapterVerses,
+    segments::{
+        parser::components::Onward,
+        segments::Segments,
+        units::{
+            chapter_range::ChapterRange, chapter_verse::ChapterVerse,
+            chapter_verse_onward::ChapterVerseOnward, chapter_verse_range::ChapterVerseRange,
+            full_book::FullBook, full_chapter::FullChapter, full_chapter_range::FullChapterRange,
+            full_chapter_verse_range::FullChapterVerseRange,
+        },
+        verse_bounds::VerseBounds,
     },
-    verse_bounds::VerseBounds,
 };
 
 /// Remember, these correspond to
@@ -31,7 +39,7 @@ use crate::segments::{
 /// Chapter Range:       `7:7-8:8`                          |
 /// --------------------------------------------------------+
 /// ```
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Segment {
     /// - This is a single chapter/verse reference
@@ -40,6 +48,9 @@ pub enum Segment {
     /// - This is a range of verse references within a single chapter
     /// - Ex: `1:2-3` `John 1:2-3`
     ChapterVerseRange(ChapterVerseRange),
+    /// - This is a verse through the end of its chapter
+    /// - Ex: `8:28ff.` in `Romans 8:28ff.`
+    ChapterVerseOnward(ChapterVerseOnward),
     /// - This is a range of verse references across a multiple chapters
     /// - Ex: `John 1:2-3:4`
     ChapterRange(ChapterRange),
@@ -54,17 +65,46 @@ pub enum Segment {
     // Actually this might not even have to exist if I have `FullChapterVerseRange` on
     // `VerboseSegmentPair`
     FullChapterVerseRange(FullChapterVerseRange),
+    /// - This is the entire book, with no chapter
+    /// - Ex: `the book of Ruth`
+    FullBook(FullBook),
+}
+
+/// - Canonical order: by where the segment starts, then by where it ends
+/// - A missing ending verse means "to the end of the chapter", so it sorts after any explicit verse
+/// - Different segments can cover the same verses (`1:1ff.` and `1`, or `1:1` and `1:1a`), so
+/// ties are broken by variant, then subverses, then the rest of their fields, like [`PartialEq`]
+impl Ord for Segment {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let end_verse = |seg: &Segment| seg.ending_verse().unwrap_or(u8::MAX);
+        self.starting_chapter()
+            .cmp(&other.starting_chapter())
+            .then(self.starting_verse().cmp(&other.starting_verse()))
+            .then(self.ending_chapter().cmp(&other.ending_chapter()))
+            .then(end_verse(self).cmp(&end_verse(other)))
+            .then(self.variant_rank().cmp(&other.variant_rank()))
+            .then(self.starting_subverse().cmp(&other.starting_subverse()))
+            .then(self.ending_subverse().cmp(&other.ending_subverse()))
+            .then_with(|| match (self, other) {
+                (Segment::ChapterVerse(a), Segment::ChapterVerse(b)) => Ord::cmp(a, b),
+                (Segment::ChapterVerseRange(a), Segment::ChapterVerseRange(b)) => Ord::cmp(a, b),
+                (Segment::ChapterVerseOnward(a), Segment::ChapterVerseOnward(b)) => Ord::cmp(a, b),
+                (Segment::ChapterRange(a), Segment::ChapterRange(b)) => Ord::cmp(a, b),
+                (Segment::FullChapter(a), Segment::FullChapter(b)) => Ord::cmp(a, b),
+                (Segment::FullChapterRange(a), Segment::FullChapterRange(b)) => Ord::cmp(a, b),
+                (Segment::FullChapterVerseRange(a), Segment::FullChapterVerseRange(b)) => {
+                    Ord::cmp(a, b)
+                }
+                (Segment::FullBook(a), Segment::FullBook(b)) => Ord::cmp(a, b),
+                // different variants were already ordered by their rank
+                _ => Ordering::Equal,
+            })
+    }
 }
 
 impl PartialOrd for Segment {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(
-            self.starting_chapter()
-                .cmp(&other.starting_chapter())
-                .then(self.starting_verse().cmp(&other.starting_verse()))
-                .then(self.ending_chapter().cmp(&other.ending_chapter()))
-                .then(self.ending_verse().cmp(&other.ending_verse())),
-        )
+        Some(self.cmp(other))
     }
 }
 
@@ -73,10 +113,12 @@ impl VerseBounds for Segment {
         match self {
             Segment::ChapterVerse(chapter_verse) => chapter_verse.starting_chapter(),
             Segment::ChapterVerseRange(chapter_range) => chapter_range.starting_chapter(),
+            Segment::ChapterVerseOnward(v) => v.starting_chapter(),
             Segment::ChapterRange(book_range) => book_range.starting_chapter(),
             Segment::FullChapter(full_chapter) => full_chapter.starting_chapter(),
             Segment::FullChapterRange(full_chapter_range) => full_chapter_range.starting_chapter(),
             Segment::FullChapterVerseRange(v) => v.starting_chapter(),
+            Segment::FullBook(full_book) => full_book.starting_chapter(),
         }
     }
 
@@ -84,10 +126,12 @@ impl VerseBounds for Segment {
         match self {
             Segment::ChapterVerse(chapter_verse) => chapter_verse.starting_verse(),
             Segment::ChapterVerseRange(chapter_range) => chapter_range.starting_verse(),
+            Segment::ChapterVerseOnward(v) => v.starting_verse(),
             Segment::ChapterRange(book_range) => book_range.starting_verse(),
             Segment::FullChapter(full_chapter) => full_chapter.starting_verse(),
             Segment::FullChapterRange(full_chapter_range) => full_chapter_range.starting_verse(),
             Segment::FullChapterVerseRange(v) => v.starting_verse(),
+            Segment::FullBook(full_book) => full_book.starting_verse(),
         }
     }
 
@@ -95,10 +139,12 @@ impl VerseBounds for Segment {
         match self {
             Segment::ChapterVerse(chapter_verse) => chapter_verse.ending_chapter(),
             Segment::ChapterVerseRange(chapter_range) => chapter_range.ending_chapter(),
+            Segment::ChapterVerseOnward(v) => v.ending_chapter(),
             Segment::ChapterRange(book_range) => book_range.ending_chapter(),
             Segment::FullChapter(full_chapter) => full_chapter.ending_chapter(),
             Segment::FullChapterRange(full_chapter_range) => full_chapter_range.ending_chapter(),
             Segment::FullChapterVerseRange(v) => v.ending_chapter(),
+            Segment::FullBook(full_book) => full_book.ending_chapter(),
         }
     }
 
@@ -106,10 +152,38 @@ impl VerseBounds for Segment {
         match self {
             Segment::ChapterVerse(chapter_verse) => chapter_verse.ending_verse(),
             Segment::ChapterVerseRange(chapter_range) => chapter_range.ending_verse(),
+            Segment::ChapterVerseOnward(v) => v.ending_verse(),
             Segment::ChapterRange(book_range) => book_range.ending_verse(),
             Segment::FullChapter(full_chapter) => full_chapter.ending_verse(),
             Segment::FullChapterRange(full_chapter_range) => full_chapter_range.ending_verse(),
             Segment::FullChapterVerseRange(v) => v.ending_verse(),
+            Segment::FullBook(full_book) => full_book.ending_verse(),
+        }
+    }
+
+    fn starting_subverse(&self) -> Option<char> {
+        match self {
+            Segment::ChapterVerse(chapter_verse) => chapter_verse.starting_subverse(),
+            Segment::ChapterVerseRange(chapter_range) => chapter_range.starting_subverse(),
+            Segment::ChapterVerseOnward(v) => v.starting_subverse(),
+            Segment::ChapterRange(book_range) => book_range.starting_subverse(),
+            Segment::FullChapter(full_chapter) => full_chapter.starting_subverse(),
+            Segment::FullChapterRange(full_chapter_range) => full_chapter_range.starting_subverse(),
+            Segment::FullChapterVerseRange(v) => v.starting_subverse(),
+            Segment::FullBook(full_book) => full_book.starting_subverse(),
+        }
+    }
+
+    fn ending_subverse(&self) -> Option<char> {
+        match self {
+            Segment::ChapterVerse(chapter_verse) => chapter_verse.ending_subverse(),
+            Segment::ChapterVerseRange(chapter_range) => chapter_range.ending_subverse(),
+            Segment::ChapterVerseOnward(v) => v.ending_subverse(),
+            Segment::ChapterRange(book_range) => book_range.ending_subverse(),
+            Segment::FullChapter(full_chapter) => full_chapter.ending_subverse(),
+            Segment::FullChapterRange(full_chapter_range) => full_chapter_range.ending_subverse(),
+            Segment::FullChapterVerseRange(v) => v.ending_subverse(),
+            Segment::FullBook(full_book) => full_book.ending_subverse(),
         }
     }
 }
@@ -136,13 +210,20 @@ impl Segment {
         end_verse: u8,
     ) -> Self {
         let cr = ChapterRange::new(start_chapter, start_verse, end_chapter, end_verse);
-        if let Some(ChapterVerseRange { chapter, verses }) = cr.as_chapter_verse_range() {
+        if let Some(ChapterVerseRange {
+            chapter, verses, ..
+        }) = cr.as_chapter_verse_range()
+        {
             Self::chapter_verse_range(chapter, verses.start, verses.end)
         } else {
             Self::ChapterRange(cr)
         }
     }
 
+    pub fn chapter_verse_onward(chapter: u8, verse: u8) -> Self {
+        Self::ChapterVerseOnward(ChapterVerseOnward::new(chapter, verse))
+    }
+
     pub fn full_chapter(chapter: u8) -> Self {
         Self::FullChapter(FullChapter::new(chapter))
     }
@@ -151,6 +232,112 @@ impl Segment {
         Self::FullChapterRange(FullChapterRange::new(start, end))
     }
 
+    pub fn full_book() -> Self {
+        Self::FullBook(FullBook)
+    }
+
+    /**
+    - Puts subverses on the verse endpoints (`3:23a`, `3:23b-25a`)
+    - Endpoints that are whole chapters have no subverse, so theirs is ignored
+    - A single verse with two different subverses (`16a-b`) becomes a range
+    */
+    pub fn with_subverses(self, start: Option<char>, end: Option<char>) -> Self {
+        match self {
+            Segment::ChapterVerse(cv) if start == end => {
+                Segment::ChapterVerse(cv.with_subverse(start))
+            }
+            Segment::ChapterVerse(cv) => Segment::ChapterVerseRange(
+                ChapterVerseRange::new(cv.chapter, cv.verse, cv.verse).with_subverses(start, end),
+            ),
+            Segment::ChapterVerseRange(cvr) => {
+                Segment::ChapterVerseRange(cvr.with_subverses(start, end))
+            }
+            Segment::ChapterRange(cr) => Segment::ChapterRange(cr.with_subverses(start, end)),
+            Segment::FullChapterVerseRange(mut fcvr) => {
+                fcvr.end.subverse = end;
+                Segment::FullChapterVerseRange(fcvr)
+            }
+            // `28ff.` is always the rest of the chapter
+            Segment::ChapterVerseOnward(_)
+            | Segment::FullChapter(_)
+            | Segment::FullChapterRange(_)
+            | Segment::FullBook(_) => self,
+        }
+    }
+
+    pub fn without_subverses(self) -> Self {
+        self.with_subverses(None, None)
+    }
+
+    /// - Turns `28ff.` into `28-39` using the verse counts of the book it's in
+    /// - Everything else (including chapters the book doesn't have) is left as is
+    pub fn resolve_onward(self, chapter_verses: &ChapterVerses) -> Self {
+        match self {
+            Segment::ChapterVerseOnward(onward) => onward.resolve(chapter_verses).unwrap_or(self),
+            _ => self,
+        }
+    }
+
+    /**
+    - What a `f.`/`ff.` after this segment covers, using the chapter and verse counts of the book
+    it's in
+    - After a chapter, they're the chapters after it: `Rom 8f.` is `Rom 8-9`, `Rom 8ff.` is
+    `Rom 8-16`
+    - After a verse, `f.` stops at the end of the chapter: `Rom 8:39f.` is `Rom 8:39`
+    - Everything else (`28ff.` is handled by [`resolve_onward`](Self::resolve_onward)) is left as is
+    */
+    pub fn resolve_following(self, onward: Onward, chapter_verses: &ChapterVerses) -> Self {
+        let last_chapter = chapter_verses.get_chapter_count();
+        match (self, onward) {
+            (Segment::FullChapter(fc), _) if fc.chapter >= last_chapter => self,
+            (Segment::FullChapter(fc), Onward::NextVerse) => {
+                Segment::full_chapter_range(fc.chapter, fc.chapter + 1)
+            }
+            (Segment::FullChapter(fc), Onward::Chapter) => {
+                Segment::full_chapter_range(fc.chapter, last_chapter)
+            }
+            (Segment::ChapterVerseRange(cvr), Onward::NextVerse)
+                if chapter_verses
+                    .get_last_verse(cvr.chapter)
+                    .is_some_and(|last| cvr.verses.start == last) =>
+            {
+                Segment::chapter_verse(cvr.chapter, cvr.verses.start)
+                    .with_subverses(cvr.subverses.start, None)
+            }
+            _ => self,
+        }
+    }
+
+    /**
+    - Turns a whole book into its chapters (`Ruth` is `Ruth 1-4`, `Jude` is `Jude 1`) using the
+    chapter counts of the book it's in
+    - Everything else is left as is
+    - Use this instead of the [`FullBook`](crate::segments::units::full_book::FullBook) bounds,
+    which don't know where the book ends
+    */
+    pub fn resolve_full_book(self, chapter_verses: &ChapterVerses) -> Self {
+        match (self, chapter_verses.get_chapter_count()) {
+            (Segment::FullBook(_), 0) => self,
+            (Segment::FullBook(_), 1) => Segment::full_chapter(1),
+            (Segment::FullBook(_), last) => Segment::full_chapter_range(1, last),
+            _ => self,
+        }
+    }
+
+    /// The order of the variants, for segments that cover the same verses
+    fn variant_rank(&self) -> u8 {
+        match self {
+            Segment::ChapterVerse(_) => 0,
+            Segment::ChapterVerseRange(_) => 1,
+            Segment::ChapterVerseOnward(_) => 2,
+            Segment::ChapterRange(_) => 3,
+            Segment::FullChapter(_) => 4,
+            Segment::FullChapterRange(_) => 5,
+            Segment::FullChapterVerseRange(_) => 6,
+            Segment::FullBook(_) => 7,
+        }
+    }
+
     pub fn as_segments(self) -> Segments {
         Segments(vec![self])
     }
@@ -159,9 +346,11 @@ impl Segment {
         match self {
             Segment::ChapterVerse(_) | Segment::FullChapter(_) => false,
             Segment::ChapterVerseRange(_)
+            | Segment::ChapterVerseOnward(_)
             | Segment::ChapterRange(_)
             | Segment::FullChapterRange(_)
-            | Segment::FullChapterVerseRange(_) => true,
+            | Segment::FullChapterVerseRange(_)
+            | Segment::FullBook(_) => true,
         }
     }
 }
@@ -177,12 +366,14 @@ impl ChapterlessFormat for Segment {
             Segment::ChapterVerseRange(chapter_verse_range) => {
                 chapter_verse_range.chapterless_format()
             }
+            Segment::ChapterVerseOnward(v) => v.chapterless_format(),
             Segment::ChapterRange(chapter_range) => chapter_range.chapterless_format(),
             Segment::FullChapter(full_chapter) => full_chapter.chapterless_format(),
             Segment::FullChapterRange(full_chapter_range) => {
                 full_chapter_range.chapterless_format()
             }
             Segment::FullChapterVerseRange(v) => v.chapterless_format(),
+            Segment::FullBook(full_book) => full_book.chapterless_format(),
         }
     }
 }
@@ -196,10 +387,12 @@ impl Display for Segment {
             match self {
                 Segment::ChapterVerse(chapter_verse) => chapter_verse.to_string(),
                 Segment::ChapterVerseRange(chapter_verse_range) => chapter_verse_range.to_string(),
+                Segment::ChapterVerseOnward(v) => v.to_string(),
                 Segment::ChapterRange(chapter_range) => chapter_range.to_string(),
                 Segment::FullChapter(full_chapter) => full_chapter.to_string(),
                 Segment::FullChapterRange(full_chapter_range) => full_chapter_range.to_string(),
                 Segment::FullChapterVerseRange(v) => v.to_string(),
+                Segment::FullBook(full_book) => full_book.to_string(),
             }
         )
     }
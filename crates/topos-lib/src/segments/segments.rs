@@ -1,11 +1,14 @@
 use derive_more::{Deref, DerefMut, IntoIterator};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    data::books::BookId,
+    data::{books::BookId, chapter_verses::ChapterVerses},
     segments::{
+        formatter::options::{DelimeterOptions, FormatOptions},
         parser::{
-            minimal::MinimalSegments,
+            components::Onward,
+            minimal::{MinimalNumber, MinimalSegments},
             verbose::{
                 VerboseFullSegment, VerboseSegments,
                 components::{DelimitedNumber, FrontPadded},
@@ -16,7 +19,9 @@ use crate::{
     },
 };
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// - Passages are ordered canonically: by book, then by their segments
+/// - This is the order used when grouping output by passage
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Passage {
     pub book: BookId,
     pub segments: Segments,
@@ -42,7 +47,19 @@ impl Passage {
 
 /// TODO: I need Segments and PartialSegments/Incomplete segments to be unified under a large
 /// Segment type that I can use for auto-completions
-#[derive(Clone, Debug, Deref, DerefMut, Serialize, Deserialize, IntoIterator)]
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Deref,
+    DerefMut,
+    Serialize,
+    Deserialize,
+    IntoIterator,
+)]
 pub struct Segments(pub Vec<Segment>);
 
 impl Segments {
@@ -73,6 +90,24 @@ impl Segments {
         }
     }
 
+    /// `ff.` resolved to the last verse of its chapter, see [`Segment::resolve_onward`]
+    pub fn resolve_onward(&self, chapter_verses: &ChapterVerses) -> Self {
+        Segments(
+            self.iter()
+                .map(|seg| seg.resolve_onward(chapter_verses))
+                .collect(),
+        )
+    }
+
+    /// Whole books resolved to their chapters, see [`Segment::resolve_full_book`]
+    pub fn resolve_full_books(&self, chapter_verses: &ChapterVerses) -> Self {
+        Segments(
+            self.iter()
+                .map(|seg| seg.resolve_full_book(chapter_verses))
+                .collect(),
+        )
+    }
+
     pub fn with_suggestion(&self, segment: Segment) -> Self {
         let mut new = self.clone();
         new.push(segment);
@@ -102,6 +137,67 @@ impl Segments {
         }
         output
     }
+
+    /// - [`Segments::format`], but with the delimeters and subverses from `options`
+    /// - The rest of the options are for formatting the user's own text, so they're ignored here
+    pub fn format_with_options(&self, options: &FormatOptions) -> String {
+        let (verse_seperator, chapter_seperator) = Self::seperators(options);
+        self.with_options(options)
+            .format(verse_seperator, chapter_seperator)
+    }
+
+    /**
+    - [`Segments::format_with_options`], for a passage in a book with only one chapter if
+    `one_chapter`
+    - Then verses are written without the chapter (`Jude 5`, `Philemon 4-6`), unless
+    [`RangeOptions::use_chapter_in_single_chapter_books`](crate::segments::formatter::options::RangeOptions::use_chapter_in_single_chapter_books)
+    - The whole chapter is the whole book, so it's empty like [`Segment::full_book`] (a bare `1`
+    would be verse 1)
+    */
+    pub fn format_in_book(&self, options: &FormatOptions, one_chapter: bool) -> String {
+        if !one_chapter || options.range.use_chapter_in_single_chapter_books {
+            return self.format_with_options(options);
+        }
+        if self.iter().all(|seg| *seg == Segment::full_chapter(1)) {
+            return String::new();
+        }
+        let only_verses = self.iter().all(|seg| {
+            seg.ending_chapter() == 1
+                && !matches!(seg, Segment::FullChapter(_) | Segment::FullBook(_))
+        });
+        if !only_verses {
+            return self.format_with_options(options);
+        }
+        let (verse_seperator, _) = Self::seperators(options);
+        self.with_options(options)
+            .iter()
+            .map(|seg| seg.chapterless_format())
+            .join(verse_seperator)
+    }
+
+    /// The verse and chapter segment seperators
+    fn seperators(options: &FormatOptions) -> (&str, &str) {
+        match &options.delim {
+            DelimeterOptions::NormalizeWith {
+                verse_segment,
+                chapter_segment,
+                ..
+            } => (
+                verse_segment.as_deref().unwrap_or(","),
+                chapter_segment.as_deref().unwrap_or("; "),
+            ),
+            _ => (",", "; "),
+        }
+    }
+
+    /// Without subverses, unless `options` includes them
+    fn with_options(&self, options: &FormatOptions) -> Segments {
+        if options.include_subverse {
+            self.clone()
+        } else {
+            Segments(self.iter().map(|seg| seg.without_subverses()).collect())
+        }
+    }
 }
 
 impl Segments {
@@ -127,63 +223,109 @@ impl std::fmt::Display for Segments {
 impl From<MinimalSegments> for Segments {
     fn from(value: MinimalSegments) -> Self {
         let mut segments = Segments::new();
+        segments.extend_minimal(value);
+        segments
+    }
+}
+
+impl Segments {
+    /// - Bare numbers are read as verses of `chapter`, like they would be after a `chapter:verse`
+    /// - This is for `v. 16` and `vv. 18-20`, where the chapter comes from context
+    pub fn from_minimal_in_chapter(value: MinimalSegments, chapter: u8) -> Self {
+        let mut segments = Segments(vec![Segment::full_chapter(chapter)]);
+        segments.extend_minimal(value);
+        segments.remove(0);
+        segments
+    }
+
+    /// - Bare numbers and ranges are relative to the last segment (if there is one)
+    /// - Subverses are kept on the verse endpoints (`3:23a`)
+    /// - A single verse can be followed by `f.` (the next verse too) or `ff.` (the rest of the chapter)
+    /// - `f.`/`ff.` after a chapter need the chapter count, see
+    /// [`read_segments`](crate::data::chapter_verses::BookChapterVerses::read_segments)
+    fn extend_minimal(&mut self, value: MinimalSegments) {
+        let segments = self;
         for seg in value.segments {
+            let (start, end) = (seg.start, seg.end);
+            let single = |chapter: u8, verse: MinimalNumber| match seg.onward {
+                Some(Onward::NextVerse) => Segment::chapter_verse_range(
+                    chapter,
+                    verse.value,
+                    verse.value.saturating_add(1),
+                )
+                .with_subverses(verse.subverse, None),
+                Some(Onward::Chapter) => Segment::chapter_verse_onward(chapter, verse.value),
+                None => Segment::chapter_verse(chapter, verse.value)
+                    .with_subverses(verse.subverse, verse.subverse),
+            };
             let new = if let Some(start_verse) = seg.explicit_start_verse {
-                let start_chapter = seg.start;
-                if let Some(end) = seg.end {
+                let start_chapter = start.value;
+                if let Some(end) = end {
                     // `1:2-3:4`
                     if let Some(end_verse) = end.1 {
-                        let end_chapter = end.0;
-                        Segment::chapter_range(start_chapter, start_verse, end_chapter, end_verse)
+                        let end_chapter = end.0.value;
+                        Segment::chapter_range(
+                            start_chapter,
+                            start_verse.value,
+                            end_chapter,
+                            end_verse.value,
+                        )
+                        .with_subverses(start_verse.subverse, end_verse.subverse)
                     }
                     // `1:2-3`
                     else {
                         let end_verse = end.0;
-                        Segment::chapter_verse_range(start_chapter, start_verse, end_verse)
+                        Segment::chapter_verse_range(
+                            start_chapter,
+                            start_verse.value,
+                            end_verse.value,
+                        )
+                        .with_subverses(start_verse.subverse, end_verse.subverse)
                     }
                 // `1:2`
                 } else {
-                    Segment::chapter_verse(start_chapter, start_verse)
+                    single(start_chapter, start_verse)
                 }
             } else {
-                if let Some(end) = seg.end {
+                if let Some(end) = end {
                     // `1:2-3:4`
                     if let Some(end_verse) = end.1 {
-                        let start_chapter = seg.start;
-                        let end_chapter = end.0;
-                        Segment::chapter_range(start_chapter, 1, end_chapter, end_verse)
+                        let start_chapter = start.value;
+                        let end_chapter = end.0.value;
+                        Segment::chapter_range(start_chapter, 1, end_chapter, end_verse.value)
+                            .with_subverses(None, end_verse.subverse)
                     } else {
                         // `3:4-5`
                         if let Some(prev) = segments.last() {
-                            let start_verse = seg.start;
+                            let start_verse = start;
                             let end_verse = end.0;
                             Segment::chapter_verse_range(
                                 prev.ending_chapter(),
-                                start_verse,
-                                end_verse,
+                                start_verse.value,
+                                end_verse.value,
                             )
+                            .with_subverses(start_verse.subverse, end_verse.subverse)
                         }
                         // `1-25`
                         else {
-                            let start_chapter = seg.start;
-                            let end_chapter = end.0;
+                            let start_chapter = start.value;
+                            let end_chapter = end.0.value;
                             Segment::full_chapter_range(start_chapter, end_chapter)
                         }
                     }
                 } else {
                     // `1:1`
                     if let Some(prev) = segments.last() {
-                        Segment::chapter_verse(prev.ending_chapter(), seg.start)
+                        single(prev.ending_chapter(), start)
                     }
                     // `1`
                     else {
-                        Segment::full_chapter(seg.start)
+                        Segment::full_chapter(start.value)
                     }
                 }
             };
             segments.push(new);
         }
-        segments
     }
 }
 
@@ -260,3 +402,235 @@ impl From<MinimalSegments> for Segments {
 //         segments
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::{books::Books, chapter_verses::BookChapterVerses, data::BibleData},
+        error::AnyResult,
+        segments::{
+            formatter::options::FormatOptions,
+            segment::Segment,
+            segments::{Passage, Segments},
+            verse_bounds::VerseBounds,
+        },
+    };
+
+    #[test]
+    fn canonical_order() -> AnyResult<()> {
+        let books = Books::base();
+        let psg = |s: &str| books.parse(s).ok_or(format!("Failed to parse {s}"));
+
+        let mut passages: Vec<Passage> = vec![
+            psg("Romans 8:28")?,
+            psg("John 3:16")?,
+            psg("Genesis 1")?,
+            psg("Romans 8")?,
+            psg("Romans 3:23")?,
+            psg("Romans 8:1-4")?,
+        ];
+        passages.sort();
+
+        let expected = vec![
+            psg("Genesis 1")?,
+            psg("John 3:16")?,
+            psg("Romans 3:23")?,
+            psg("Romans 8:1-4")?,
+            psg("Romans 8")?,
+            psg("Romans 8:28")?,
+        ];
+        assert_eq!(passages, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn subverses() -> AnyResult<()> {
+        let books = Books::base();
+        let psg = |s: &str| books.parse(s).ok_or(format!("Failed to parse {s}"));
+
+        let a = psg("Rom 3:23a")?;
+        let b = psg("Rom 3:23b")?;
+        assert_ne!(a, b);
+        assert!(!a.overlaps_with(&b));
+        assert!(psg("Rom 3:23")?.contains(&a));
+        assert_eq!(a.segments.to_string(), "3:23a");
+        assert_eq!(
+            psg("Rom 3:21b-26a, 28")?.segments.to_string(),
+            "3:21b-26a,28"
+        );
+        assert_eq!(psg("Rom 3:23b-4:2a")?.segments.to_string(), "3:23b-4:2a");
+
+        let json = serde_json::to_string(&b.segments)?;
+        assert_eq!(json, r#"["3:23b"]"#);
+        assert_eq!(serde_json::from_str::<Segments>(&json)?, b.segments);
+
+        let mut options = FormatOptions::default();
+        assert_eq!(a.segments.format_with_options(&options), "3:23a");
+        options.include_subverse = false;
+        assert_eq!(a.segments.format_with_options(&options), "3:23");
+
+        Ok(())
+    }
+
+    #[test]
+    fn onward() -> AnyResult<()> {
+        let books = Books::base();
+        let psg = |s: &str| books.parse(s).ok_or(format!("Failed to parse {s}"));
+
+        let rom = psg("Rom 8:28ff")?;
+        assert_eq!(rom.segments.to_string(), "8:28ff.");
+        assert_eq!(psg("Gen 12:1f.")?.segments.to_string(), "12:1-2");
+        assert_eq!(psg("Rom 8:1, 28ff.")?.segments.to_string(), "8:1,28ff.");
+        assert!(rom.overlaps_with(&psg("Rom 8:35")?));
+        assert!(!rom.overlaps_with(&psg("Rom 8:27")?));
+
+        let json = serde_json::to_string(&rom.segments)?;
+        assert_eq!(json, r#"["8:28ff."]"#);
+        assert_eq!(serde_json::from_str::<Segments>(&json)?, rom.segments);
+
+        let chapter_verses = BookChapterVerses::default();
+        let romans = chapter_verses.get_chapter_verses(&rom.book).unwrap();
+        assert_eq!(rom.segments.resolve_onward(romans).to_string(), "8:28-39");
+
+        // with the chapter counts, `f.`/`ff.` after a chapter are the chapters after it, and
+        // they don't go past the end of the chapter or book
+        let data = BibleData::default();
+        let read = |s: &str| {
+            data.parse(s)
+                .map(|p| p.segments.to_string())
+                .ok_or(format!("Failed to parse {s}"))
+        };
+        assert_eq!(read("Rom 8ff")?, "8-16");
+        assert_eq!(read("Rom 8f.")?, "8-9");
+        assert_eq!(read("Rom 16ff.")?, "16");
+        assert_eq!(read("Rom 8:39f.")?, "8:39");
+        assert_eq!(read("Rom 8:38f.")?, "8:38-39");
+        assert_eq!(read("Jude 24f.")?, "1:24-25");
+        assert_eq!(read("Jude 25f.")?, "1:25");
+        // the end of a range doesn't take them
+        assert_eq!(read("Rom 8:28-30ff")?, "8:28-30");
+        assert_eq!(read("Rom 8-9ff.")?, "8-9");
+
+        Ok(())
+    }
+
+    #[test]
+    fn segment_order() {
+        let mut segments = vec![
+            Segment::full_book(),
+            Segment::full_chapter(1),
+            Segment::chapter_verse_onward(1, 1),
+            Segment::chapter_verse_range(1, 1, 3),
+            Segment::chapter_verse(1, 1).with_subverses(Some('a'), Some('a')),
+            Segment::chapter_verse(1, 1),
+            Segment::full_chapter_range(1, 2),
+        ];
+        segments.sort();
+        let order: Vec<String> = segments.iter().map(|seg| seg.to_string()).collect();
+        assert_eq!(
+            order,
+            vec!["1:1", "1:1a", "1:1-3", "1:1ff.", "1", "1-2", ""]
+        );
+
+        // only equal segments compare equal
+        for (i, a) in segments.iter().enumerate() {
+            for (j, b) in segments.iter().enumerate() {
+                assert_eq!(a.cmp(b) == std::cmp::Ordering::Equal, i == j);
+            }
+        }
+    }
+
+    #[test]
+    fn full_book() -> AnyResult<()> {
+        let books = Books::base();
+        let psg = |s: &str| books.parse(s).ok_or(format!("Failed to parse {s}"));
+
+        let ruth = psg("Ruth")?;
+        assert_eq!(ruth.segments.to_string(), "");
+        assert_eq!(
+            BibleData::default().format_passage(&ruth).as_deref(),
+            Some("Ruth")
+        );
+        assert!(ruth.overlaps_with(&psg("Ruth 4:22")?));
+        assert!(!ruth.overlaps_with(&psg("Esther 1:1")?));
+        assert!(ruth.segments[0].fully_contains(&psg("Ruth 2:1-3")?.segments[0]));
+
+        let json = serde_json::to_string(&ruth.segments)?;
+        assert_eq!(json, r#"[""]"#);
+        assert_eq!(serde_json::from_str::<Segments>(&json)?, ruth.segments);
+
+        let chapter_verses = BookChapterVerses::default();
+        let ordinals = chapter_verses.passage_ordinals(&ruth);
+        assert_eq!(ordinals.len(), 1);
+        assert_eq!(
+            ordinals[0].end - ordinals[0].start + 1,
+            chapter_verses
+                .get_chapter_verses(&ruth.book)
+                .unwrap()
+                .get_verse_count()
+        );
+
+        // its real chapters, not the highest one possible
+        let ruth_verses = chapter_verses.get_chapter_verses(&ruth.book).unwrap();
+        assert_eq!(
+            ruth.segments.resolve_full_books(ruth_verses).to_string(),
+            "1-4"
+        );
+        let jude = psg("Jude")?;
+        let jude_verses = chapter_verses.get_chapter_verses(&jude.book).unwrap();
+        assert_eq!(
+            jude.segments.resolve_full_books(jude_verses).to_string(),
+            "1"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn single_chapter_books() -> AnyResult<()> {
+        let data = BibleData::default();
+        let psg = |s: &str| data.parse(s).ok_or(format!("Failed to parse {s}"));
+
+        assert_eq!(psg("Jude 5")?.segments.to_string(), "1:5");
+        assert_eq!(psg("Philemon 4-6")?.segments.to_string(), "1:4-6");
+        assert_eq!(psg("3 John 14")?.segments.to_string(), "1:14");
+        assert_eq!(psg("Jude 3, 5-7")?.segments.to_string(), "1:3,5-7");
+        assert_eq!(psg("Jude 1:5")?.segments.to_string(), "1:5");
+        // books with more than one chapter are unchanged
+        assert_eq!(psg("Romans 8")?.segments.to_string(), "8");
+        assert_eq!(
+            Books::base()
+                .parse("Jude 5")
+                .map(|p| p.segments.to_string()),
+            Some("5".to_string())
+        );
+
+        let format = |s: &str| psg(s).map(|p| data.format_passage(&p));
+        assert_eq!(format("Jude 5")?.as_deref(), Some("Jude 5"));
+        assert_eq!(format("Philemon 4-6")?.as_deref(), Some("Philemon 4-6"));
+        assert_eq!(format("Romans 8:28")?.as_deref(), Some("Romans 8:28"));
+        let jude = Segments(vec![Segment::full_chapter(1)]).with_book(psg("Jude")?.book);
+        assert_eq!(data.format_passage(&jude).as_deref(), Some("Jude"));
+
+        let mut options = FormatOptions::default();
+        options.range.use_chapter_in_single_chapter_books = true;
+        assert_eq!(
+            data.format_passage_with(&psg("Jude 5")?, &options)
+                .as_deref(),
+            Some("Jude 1:5")
+        );
+
+        // a range reads each end in its own book
+        let range = data
+            .parse_range("Philemon 25 - Hebrews 1:2")
+            .ok_or("Failed to parse range")?;
+        assert_eq!(range.start.segments.to_string(), "1:25");
+        assert_eq!(
+            data.format_range(&range).as_deref(),
+            Some("Philemon 25 – Hebrews 1:2")
+        );
+
+        Ok(())
+    }
+}
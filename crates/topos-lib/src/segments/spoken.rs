@@ -0,0 +1,349 @@
+use crate::segments::{segment::Segment, segments::Segments};
+
+/// The most words a spoken reference can take, so a long run of numbers doesn't get scanned forever
+const MAX_WORDS: usize = 32;
+
+const ONES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// A lowercase word and where it ends (in bytes, from the start of the input)
+#[derive(Clone, Debug)]
+struct Word {
+    text: String,
+    end: usize,
+}
+
+/**
+- Segments that were said out loud, like the ones in a transcript
+- `chapter three, verses sixteen through eighteen` => `3:16-18`
+- `two one` => `2:1`
+- `chapters three to five` => `3-5`
+- `three sixteen and eighteen` => `3:16,18`
+- `chapter three verse sixteen through chapter four verse two` => `3:16-4:2`
+- Numbers may be words (`one hundred and nineteen`, `twenty-one`) or digits
+*/
+#[derive(Clone, Debug)]
+pub struct SpokenSegments {
+    pub segments: Segments,
+    /// How many bytes of the input this took, so the match can be located
+    len: usize,
+}
+
+impl SpokenSegments {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /**
+    - This is meant for the text right after a book name, so leading whitespace is fine
+    - A lone number (`Mark two`) is too likely to be regular speech, so there must be a verse, or
+    the word `chapter`
+    */
+    pub fn parse(input: &str) -> Option<Self> {
+        let words = Self::words(input);
+        let mut parser = SpokenParser {
+            words: &words,
+            idx: 0,
+            end: 0,
+        };
+        let segments = parser.segments()?;
+        Some(Self {
+            segments,
+            len: parser.end,
+        })
+    }
+
+    /// - Words are separated by whitespace, commas, or hyphens (`twenty-one`)
+    /// - Anything else (like a period) ends the reference
+    fn words(input: &str) -> Vec<Word> {
+        let mut words = vec![];
+        let mut chars = input.char_indices().peekable();
+        while words.len() < MAX_WORDS {
+            while chars
+                .next_if(|(_, c)| c.is_whitespace() || *c == ',' || *c == '-')
+                .is_some()
+            {}
+            let Some(&(start, c)) = chars.peek() else {
+                break;
+            };
+            if !c.is_alphanumeric() {
+                break;
+            }
+            let mut end = start;
+            while let Some((idx, c)) = chars.next_if(|(_, c)| c.is_alphanumeric()) {
+                end = idx + c.len_utf8();
+            }
+            words.push(Word {
+                text: input[start..end].to_lowercase(),
+                end,
+            });
+        }
+        words
+    }
+}
+
+struct SpokenParser<'a> {
+    words: &'a [Word],
+    idx: usize,
+    /// The end of the last word that was part of the reference
+    end: usize,
+}
+
+impl SpokenParser<'_> {
+    fn peek(&self, offset: usize) -> Option<&str> {
+        self.words.get(self.idx + offset).map(|w| w.text.as_str())
+    }
+
+    fn is(&self, offset: usize, options: &[&str]) -> bool {
+        self.peek(offset).is_some_and(|w| options.contains(&w))
+    }
+
+    fn advance(&mut self, count: usize) {
+        self.idx += count;
+        self.end = self.words[self.idx - 1].end;
+    }
+
+    fn segments(&mut self) -> Option<Segments> {
+        let chapter_keyword = self.is(0, &["chapter", "chapters"]);
+        if chapter_keyword {
+            self.advance(1);
+        }
+        let chapter = self.number()?;
+
+        let verse_keyword = self.is(0, &["verse", "verses"]);
+        if verse_keyword {
+            self.advance(1);
+        }
+        let mut segments = Segments::new();
+
+        let Some(first) = self.item(chapter) else {
+            if verse_keyword || !chapter_keyword {
+                return None;
+            }
+            // `chapters three through five`
+            let seg = match self.through(|p| p.number()) {
+                Some(end) if end > chapter => Segment::full_chapter_range(chapter, end),
+                _ => Segment::full_chapter(chapter),
+            };
+            return Some(seg.as_segments());
+        };
+        segments.push(first);
+
+        // `and seventeen`, `and verse twenty`
+        loop {
+            let skip = if self.is(0, &["and"]) && self.is(1, &["verse", "verses"]) {
+                2
+            } else if self.is(0, &["and"]) {
+                1
+            } else {
+                break;
+            };
+            let (idx, end) = (self.idx, self.end);
+            self.advance(skip);
+            match self.item(chapter) {
+                Some(seg) => segments.push(seg),
+                None => {
+                    // that `and` belongs to the rest of the sentence
+                    self.idx = idx;
+                    self.end = end;
+                    break;
+                }
+            }
+        }
+
+        Some(segments)
+    }
+
+    /// A verse, or a range starting at a verse
+    fn item(&mut self, chapter: u8) -> Option<Segment> {
+        let verse = self.number()?;
+
+        // `through chapter four verse two`
+        let cross_chapter = self.through(|p| {
+            if !p.is(0, &["chapter"]) {
+                return None;
+            }
+            p.advance(1);
+            let end_chapter = p.number()?;
+            if p.is(0, &["verse"]) {
+                p.advance(1);
+            }
+            let end_verse = p.number()?;
+            (end_chapter > chapter).then_some((end_chapter, end_verse))
+        });
+        if let Some((end_chapter, end_verse)) = cross_chapter {
+            return Some(Segment::chapter_range(
+                chapter,
+                verse,
+                end_chapter,
+                end_verse,
+            ));
+        }
+
+        let end_verse = self.through(|p| p.number().filter(|end| *end > verse));
+        Some(match end_verse {
+            Some(end_verse) => Segment::chapter_verse_range(chapter, verse, end_verse),
+            None => Segment::chapter_verse(chapter, verse),
+        })
+    }
+
+    /// - `through`/`to`/`thru` followed by whatever `end` parses
+    /// - Nothing is consumed if `end` fails
+    fn through<T>(&mut self, end: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        if !self.is(0, &["through", "thru", "to"]) {
+            return None;
+        }
+        let (idx, prev_end) = (self.idx, self.end);
+        self.advance(1);
+        let res = end(self);
+        if res.is_none() {
+            self.idx = idx;
+            self.end = prev_end;
+        }
+        res
+    }
+
+    /// - Digits, or number words up to `two hundred and fifty-five`
+    /// - `three sixteen` is two numbers, but `twenty one` is one
+    fn number(&mut self) -> Option<u8> {
+        let word = self.peek(0)?;
+        if word.chars().all(|c| c.is_ascii_digit()) {
+            let value = word.parse().ok()?;
+            self.advance(1);
+            return Some(value);
+        }
+
+        let hundreds = if self.is(0, &["hundred"]) {
+            Some((1, 1))
+        } else if self.is(1, &["hundred"]) {
+            let value = if word == "a" {
+                Some(1)
+            } else {
+                Self::position(&ONES, word)
+            };
+            value.map(|value| (value, 2))
+        } else {
+            None
+        };
+
+        let Some((hundreds, len)) = hundreds else {
+            return self
+                .below_hundred()
+                .and_then(|value| u8::try_from(value).ok());
+        };
+        self.advance(len);
+        let mut value = hundreds * 100;
+        // `one hundred and nineteen`, but not `one hundred and Romans`
+        let skip = if self.is(0, &["and"]) { 1 } else { 0 };
+        let (idx, end) = (self.idx, self.end);
+        if skip > 0 {
+            self.advance(skip);
+        }
+        match self.below_hundred() {
+            Some(rest) => value += rest,
+            None => {
+                self.idx = idx;
+                self.end = end;
+            }
+        }
+        u8::try_from(value).ok()
+    }
+
+    fn below_hundred(&mut self) -> Option<u32> {
+        let word = self.peek(0)?;
+        if let Some(tens) = Self::position(&TENS, word) {
+            self.advance(1);
+            // `twenty` is the first of the tens
+            let mut value = (tens + 1) * 10;
+            if let Some(ones) = self.peek(0).and_then(|w| Self::position(&ONES, w)) {
+                self.advance(1);
+                value += ones;
+            }
+            return Some(value);
+        }
+        let value = Self::position(&TEENS, word)
+            .map(|teen| teen + 9)
+            .or_else(|| Self::position(&ONES, word))?;
+        self.advance(1);
+        Some(value)
+    }
+
+    /// Where `word` is in `words`, starting at 1
+    fn position(words: &[&str], word: &str) -> Option<u32> {
+        let idx = words.iter().position(|w| *w == word)?;
+        Some(idx as u32 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::spoken::SpokenSegments;
+
+    fn spoken(input: &str) -> Option<(String, &str)> {
+        SpokenSegments::parse(input).map(|s| (s.segments.to_string(), &input[s.len()..]))
+    }
+
+    #[test]
+    fn spoken_segments() {
+        let parse = |input: &str| spoken(input).map(|(segments, _)| segments);
+
+        assert_eq!(
+            parse(" chapter three, verses sixteen through eighteen").as_deref(),
+            Some("3:16-18")
+        );
+        assert_eq!(parse(" two one").as_deref(), Some("2:1"));
+        assert_eq!(parse(" three sixteen").as_deref(), Some("3:16"));
+        assert_eq!(parse(" chapter 3 verse 16").as_deref(), Some("3:16"));
+        assert_eq!(parse(" chapter three").as_deref(), Some("3"));
+        assert_eq!(parse(" chapters three to five").as_deref(), Some("3-5"));
+        assert_eq!(
+            parse(" three sixteen and eighteen").as_deref(),
+            Some("3:16,18")
+        );
+        assert_eq!(parse(" twenty-one twenty-five").as_deref(), Some("21:25"));
+        assert_eq!(
+            parse(" one hundred and nineteen verse one hundred and five").as_deref(),
+            Some("119:105")
+        );
+        assert_eq!(
+            parse(" chapter three verse sixteen through chapter four verse two").as_deref(),
+            Some("3:16-4:2")
+        );
+
+        // a lone number is too ambiguous
+        assert_eq!(parse(" two things"), None);
+        assert_eq!(parse(" said"), None);
+    }
+
+    #[test]
+    fn spoken_segments_end() {
+        assert_eq!(
+            spoken(" three sixteen and then"),
+            Some(("3:16".to_string(), " and then"))
+        );
+        assert_eq!(
+            spoken(" chapter five. Then"),
+            Some(("5".to_string(), ". Then"))
+        );
+        assert_eq!(
+            spoken(" two one to the church"),
+            Some(("2:1".to_string(), " to the church"))
+        );
+    }
+}
@@ -21,11 +21,10 @@ pub struct ChapterRange(RangePair<ChapterVerse>);
 impl ChapterRange {
     pub fn as_chapter_verse_range(&self) -> Option<ChapterVerseRange> {
         if self.start.chapter == self.end.chapter {
-            Some(ChapterVerseRange::new(
-                self.start.chapter,
-                self.start.verse,
-                self.end.verse,
-            ))
+            Some(
+                ChapterVerseRange::new(self.start.chapter, self.start.verse, self.end.verse)
+                    .with_subverses(self.start.subverse, self.end.subverse),
+            )
         } else {
             None
         }
@@ -37,11 +36,7 @@ impl Display for ChapterRange {
         if let Some(cvr) = self.as_chapter_verse_range() {
             cvr.fmt(f)
         } else {
-            write!(
-                f,
-                "{}:{}-{}:{}",
-                self.start.chapter, self.start.verse, self.end.chapter, self.end.verse
-            )
+            write!(f, "{}-{}", self.start, self.end)
         }
     }
 }
@@ -51,10 +46,7 @@ impl ChapterlessFormat for ChapterRange {
         if let Some(cvr) = self.as_chapter_verse_range() {
             cvr.chapterless_format()
         } else {
-            format!(
-                "{}-{}:{}",
-                self.start.verse, self.end.chapter, self.end.verse
-            )
+            format!("{}-{}", self.start.chapterless_format(), self.end)
         }
     }
 }
@@ -134,6 +126,14 @@ impl VerseBounds for ChapterRange {
     fn ending_verse(&self) -> Option<u8> {
         Some(self.end.verse)
     }
+
+    fn starting_subverse(&self) -> Option<char> {
+        self.start.subverse
+    }
+
+    fn ending_subverse(&self) -> Option<char> {
+        self.end.subverse
+    }
 }
 
 impl PartialOrd for ChapterRange {
@@ -144,7 +144,9 @@ impl PartialOrd for ChapterRange {
                 .cmp(&other.start.chapter)
                 .then(self.start.verse.cmp(&other.start.verse))
                 .then(self.end.chapter.cmp(&other.end.chapter))
-                .then(self.end.verse.cmp(&other.end.verse)),
+                .then(self.end.verse.cmp(&other.end.verse))
+                .then(self.start.subverse.cmp(&other.start.subverse))
+                .then(self.end.subverse.cmp(&other.end.subverse)),
         )
     }
 }
@@ -169,6 +171,11 @@ impl ChapterRange {
             end: ChapterVerse::new(end_chapter, end_verse),
         })
     }
+    pub fn with_subverses(mut self, start: Option<char>, end: Option<char>) -> Self {
+        self.start.subverse = start;
+        self.end.subverse = end;
+        self
+    }
 }
 
 impl Into<Segment> for ChapterRange {
@@ -187,13 +194,21 @@ impl TryFrom<Segment> for ChapterRange {
                 chapter_verse.verse,
                 chapter_verse.chapter,
                 chapter_verse.verse,
-            ),
+            )
+            .with_subverses(chapter_verse.subverse, chapter_verse.subverse),
             Segment::ChapterVerseRange(chapter_verse_range) => ChapterRange::new(
                 chapter_verse_range.chapter,
                 chapter_verse_range.verses.start,
                 chapter_verse_range.chapter,
                 chapter_verse_range.verses.end,
+            )
+            .with_subverses(
+                chapter_verse_range.subverses.start,
+                chapter_verse_range.subverses.end,
             ),
+            Segment::ChapterVerseOnward(_) => Err(format!(
+                "Cannot coerce ChapterVerseOnward into ChapterRange"
+            ))?,
             Segment::ChapterRange(chapter_range) => chapter_range,
             Segment::FullChapter(_) => Err(format!("Cannot coerce FullChapter into ChapterRange"))?,
             Segment::FullChapterRange(_) => {
@@ -202,6 +217,7 @@ impl TryFrom<Segment> for ChapterRange {
             Segment::FullChapterVerseRange(_) => Err(format!(
                 "Cannot coerce FullChapterVerseRange into ChapterRange"
             ))?,
+            Segment::FullBook(_) => Err(format!("Cannot coerce FullBook into ChapterRange"))?,
         })
     }
 }
@@ -215,18 +231,18 @@ impl ParsableSegment for ChapterRange {
         let start_chapter = ChapterRange::take_number(chars)?;
         ChapterRange::expect_char(chars, ':')?;
         let start_verse = ChapterRange::take_number(chars)?;
+        let start_subverse = ChapterRange::take_subverse(chars);
         ChapterRange::expect_char(chars, '-')?;
         let end_chapter = ChapterRange::take_number(chars)?;
         ChapterRange::expect_char(chars, ':')?;
         let end_verse = ChapterRange::take_number(chars)?;
+        let end_subverse = ChapterRange::take_subverse(chars);
         ChapterRange::expect_done(chars)?;
 
-        Ok(ChapterRange::new(
-            start_chapter,
-            start_verse,
-            end_chapter,
-            end_verse,
-        ))
+        Ok(
+            ChapterRange::new(start_chapter, start_verse, end_chapter, end_verse)
+                .with_subverses(start_subverse, end_subverse),
+        )
     }
 }
 
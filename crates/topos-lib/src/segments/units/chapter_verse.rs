@@ -15,17 +15,23 @@ use std::{
 pub struct ChapterVerse {
     pub chapter: u8,
     pub verse: u8,
+    /// - Part of a verse
+    /// - Ex: `a` in `3:23a`
+    pub subverse: Option<char>,
 }
 
 impl Display for ChapterVerse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.chapter, self.verse)
+        write!(f, "{}:{}", self.chapter, self.chapterless_format())
     }
 }
 
 impl ChapterlessFormat for ChapterVerse {
     fn chapterless_format(&self) -> String {
-        format!("{}", self.verse)
+        match self.subverse {
+            Some(subverse) => format!("{}{}", self.verse, subverse),
+            None => format!("{}", self.verse),
+        }
     }
 }
 
@@ -100,6 +106,14 @@ impl VerseBounds for ChapterVerse {
     fn ending_verse(&self) -> Option<u8> {
         Some(self.verse)
     }
+
+    fn starting_subverse(&self) -> Option<char> {
+        self.subverse
+    }
+
+    fn ending_subverse(&self) -> Option<char> {
+        self.subverse
+    }
 }
 
 impl PartialOrd for ChapterVerse {
@@ -107,14 +121,23 @@ impl PartialOrd for ChapterVerse {
         Some(
             self.chapter
                 .cmp(&other.chapter)
-                .then(self.verse.cmp(&other.verse)),
+                .then(self.verse.cmp(&other.verse))
+                .then(self.subverse.cmp(&other.subverse)),
         )
     }
 }
 
 impl ChapterVerse {
     pub fn new(chapter: u8, verse: u8) -> Self {
-        ChapterVerse { chapter, verse }
+        ChapterVerse {
+            chapter,
+            verse,
+            subverse: None,
+        }
+    }
+    pub fn with_subverse(mut self, subverse: Option<char>) -> Self {
+        self.subverse = subverse;
+        self
     }
 }
 
@@ -133,6 +156,9 @@ impl TryFrom<Segment> for ChapterVerse {
             Segment::ChapterVerseRange(_) => {
                 Err(format!("Cannot coerce ChapterVerseRange into ChapterVerse"))?
             }
+            Segment::ChapterVerseOnward(_) => Err(format!(
+                "Cannot coerce ChapterVerseOnward into ChapterVerse"
+            ))?,
             Segment::ChapterRange(_) => {
                 Err(format!("Cannot coerce ChapterRange into ChapterVerse"))?
             }
@@ -143,6 +169,7 @@ impl TryFrom<Segment> for ChapterVerse {
             Segment::FullChapterVerseRange(_) => Err(format!(
                 "Cannot coerce FullChapterVerseRange into ChapterVerse"
             ))?,
+            Segment::FullBook(_) => Err(format!("Cannot coerce FullBook into ChapterVerse"))?,
         })
     }
 }
@@ -156,9 +183,10 @@ impl ParsableSegment for ChapterVerse {
         let chapter = ChapterVerse::take_number(chars)?;
         ChapterVerse::expect_char(chars, ':')?;
         let verse = ChapterVerse::take_number(chars)?;
+        let subverse = ChapterVerse::take_subverse(chars);
         ChapterVerse::expect_done(chars)?;
 
-        Ok(ChapterVerse::new(chapter, verse))
+        Ok(ChapterVerse::new(chapter, verse).with_subverse(subverse))
     }
 }
 
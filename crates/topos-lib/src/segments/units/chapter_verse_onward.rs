@@ -0,0 +1,174 @@
+use crate::{
+    data::chapter_verses::ChapterVerses,
+    segments::{
+        segment::{ChapterlessFormat, Segment},
+        units::parse::{ParsableSegment, SegmentParseMethods},
+        verse_bounds::VerseBounds,
+    },
+};
+use serde::{Deserialize, Serialize, de::Visitor};
+use std::{fmt::Display, str::FromStr};
+
+/// - This is a verse and everything after it in the chapter
+/// - Ex: `8:28ff.` in `Romans 8:28ff.`
+/// - It stays open-ended until it's resolved with [`ChapterVerseOnward::resolve`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChapterVerseOnward {
+    pub chapter: u8,
+    pub verse: u8,
+}
+
+impl Display for ChapterVerseOnward {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.chapter, self.chapterless_format())
+    }
+}
+
+impl ChapterlessFormat for ChapterVerseOnward {
+    fn chapterless_format(&self) -> String {
+        format!("{}ff.", self.verse)
+    }
+}
+
+impl Serialize for ChapterVerseOnward {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+struct ChapterVerseOnwardVisitor;
+
+impl<'de> Visitor<'de> for ChapterVerseOnwardVisitor {
+    type Value = ChapterVerseOnward;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("format '{}:{}ff.'")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        Ok(ChapterVerseOnward::new(
+            seq.next_element()?
+                .ok_or_else(|| serde::de::Error::custom("missing chapter"))?,
+            seq.next_element()?
+                .ok_or_else(|| serde::de::Error::custom("missing verse"))?,
+        ))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        v.parse().map_err(|e| E::custom(e))
+    }
+}
+
+impl<'de> Deserialize<'de> for ChapterVerseOnward {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ChapterVerseOnwardVisitor)
+    }
+}
+
+impl FromStr for ChapterVerseOnward {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl VerseBounds for ChapterVerseOnward {
+    fn starting_chapter(&self) -> u8 {
+        self.chapter
+    }
+
+    fn starting_verse(&self) -> u8 {
+        self.verse
+    }
+
+    fn ending_chapter(&self) -> u8 {
+        self.chapter
+    }
+
+    fn ending_verse(&self) -> Option<u8> {
+        None
+    }
+}
+
+impl ChapterVerseOnward {
+    pub fn new(chapter: u8, verse: u8) -> Self {
+        ChapterVerseOnward { chapter, verse }
+    }
+
+    /// - The verse through the last verse of the chapter
+    /// - [`None`] if the book doesn't have this chapter
+    pub fn resolve(&self, chapter_verses: &ChapterVerses) -> Option<Segment> {
+        let last_verse = chapter_verses.get_last_verse(self.chapter)?;
+        Some(Segment::chapter_verse_range(
+            self.chapter,
+            self.verse,
+            last_verse.max(self.verse),
+        ))
+    }
+}
+
+impl Into<Segment> for ChapterVerseOnward {
+    fn into(self) -> Segment {
+        Segment::ChapterVerseOnward(self)
+    }
+}
+
+impl TryFrom<Segment> for ChapterVerseOnward {
+    type Error = String;
+
+    fn try_from(value: Segment) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Segment::ChapterVerseOnward(chapter_verse_onward) => chapter_verse_onward,
+            Segment::ChapterVerse(_) => Err(format!(
+                "Cannot coerce ChapterVerse into ChapterVerseOnward"
+            ))?,
+            Segment::ChapterVerseRange(_) => Err(format!(
+                "Cannot coerce ChapterVerseRange into ChapterVerseOnward"
+            ))?,
+            Segment::ChapterRange(_) => Err(format!(
+                "Cannot coerce ChapterRange into ChapterVerseOnward"
+            ))?,
+            Segment::FullChapter(_) => {
+                Err(format!("Cannot coerce FullChapter into ChapterVerseOnward"))?
+            }
+            Segment::FullChapterRange(_) => Err(format!(
+                "Cannot coerce FullChapterRange into ChapterVerseOnward"
+            ))?,
+            Segment::FullChapterVerseRange(_) => Err(format!(
+                "Cannot coerce FullChapterVerseRange into ChapterVerseOnward"
+            ))?,
+            Segment::FullBook(_) => Err(format!("Cannot coerce FullBook into ChapterVerseOnward"))?,
+        })
+    }
+}
+
+impl ParsableSegment for ChapterVerseOnward {
+    const EXPECTED_FORMAT: &'static str = "{}:{}ff.";
+
+    fn parse_strict(input: &str) -> Result<Self, String> {
+        let chars = &mut input.chars().peekable();
+
+        let chapter = ChapterVerseOnward::take_number(chars)?;
+        ChapterVerseOnward::expect_char(chars, ':')?;
+        let verse = ChapterVerseOnward::take_number(chars)?;
+        ChapterVerseOnward::expect_char(chars, 'f')?;
+        ChapterVerseOnward::expect_char(chars, 'f')?;
+        chars.next_if_eq(&'.');
+        ChapterVerseOnward::expect_done(chars)?;
+
+        Ok(ChapterVerseOnward::new(chapter, verse))
+    }
+}
@@ -14,16 +14,28 @@ use std::{fmt::Display, str::FromStr};
 pub struct ChapterVerseRange {
     pub chapter: u8,
     pub verses: RangePair<u8>,
+    /// - The subverses of the starting and ending verse
+    /// - Ex: `a` and `b` in `3:23a-24b`
+    pub subverses: RangePair<Option<char>>,
 }
 
 impl ChapterVerseRange {
     pub fn as_chapter_verse(&self) -> Option<ChapterVerse> {
-        if self.verses.start == self.verses.end {
-            Some(ChapterVerse::new(self.chapter, self.verses.start))
+        if self.verses.start == self.verses.end && self.subverses.start == self.subverses.end {
+            Some(
+                ChapterVerse::new(self.chapter, self.verses.start)
+                    .with_subverse(self.subverses.start),
+            )
         } else {
             None
         }
     }
+    fn start(&self) -> ChapterVerse {
+        ChapterVerse::new(self.chapter, self.verses.start).with_subverse(self.subverses.start)
+    }
+    fn end(&self) -> ChapterVerse {
+        ChapterVerse::new(self.chapter, self.verses.end).with_subverse(self.subverses.end)
+    }
 }
 
 impl Display for ChapterVerseRange {
@@ -31,11 +43,7 @@ impl Display for ChapterVerseRange {
         if let Some(cv) = self.as_chapter_verse() {
             cv.fmt(f)
         } else {
-            write!(
-                f,
-                "{}:{}-{}",
-                self.chapter, self.verses.start, self.verses.end
-            )
+            write!(f, "{}:{}", self.chapter, self.chapterless_format())
         }
     }
 }
@@ -45,7 +53,11 @@ impl ChapterlessFormat for ChapterVerseRange {
         if let Some(cv) = self.as_chapter_verse() {
             cv.chapterless_format()
         } else {
-            format!("{}-{}", self.verses.start, self.verses.end)
+            format!(
+                "{}-{}",
+                self.start().chapterless_format(),
+                self.end().chapterless_format()
+            )
         }
     }
 }
@@ -123,6 +135,14 @@ impl VerseBounds for ChapterVerseRange {
     fn ending_verse(&self) -> Option<u8> {
         Some(self.verses.end)
     }
+
+    fn starting_subverse(&self) -> Option<char> {
+        self.subverses.start
+    }
+
+    fn ending_subverse(&self) -> Option<char> {
+        self.subverses.end
+    }
 }
 
 impl PartialOrd for ChapterVerseRange {
@@ -131,7 +151,8 @@ impl PartialOrd for ChapterVerseRange {
             self.chapter
                 .cmp(&other.chapter)
                 .then(self.verses.start.cmp(&other.verses.start))
-                .then(self.verses.end.cmp(&other.verses.end)),
+                .then(self.verses.end.cmp(&other.verses.end))
+                .then(self.subverses.cmp(&other.subverses)),
         )
     }
 }
@@ -144,8 +165,13 @@ impl ChapterVerseRange {
                 start: start_verse,
                 end: end_verse,
             },
+            subverses: RangePair::from_point(None),
         }
     }
+    pub fn with_subverses(mut self, start: Option<char>, end: Option<char>) -> Self {
+        self.subverses = RangePair::new(start, end);
+        self
+    }
 }
 
 impl Into<Segment> for ChapterVerseRange {
@@ -163,8 +189,12 @@ impl TryFrom<Segment> for ChapterVerseRange {
                 chapter_verse.chapter,
                 chapter_verse.verse,
                 chapter_verse.verse,
-            ),
+            )
+            .with_subverses(chapter_verse.subverse, chapter_verse.subverse),
             Segment::ChapterVerseRange(chapter_verse_range) => chapter_verse_range,
+            Segment::ChapterVerseOnward(_) => Err(format!(
+                "Cannot coerce ChapterVerseOnward into ChapterVerseRange"
+            ))?,
             Segment::ChapterRange(_) => {
                 Err(format!("Cannot coerce ChapterRange into ChapterVerseRange"))?
             }
@@ -177,6 +207,7 @@ impl TryFrom<Segment> for ChapterVerseRange {
             Segment::FullChapterVerseRange(_) => Err(format!(
                 "Cannot coerce FullChapterVerseRange into ChapterVerseRange"
             ))?,
+            Segment::FullBook(_) => Err(format!("Cannot coerce FullBook into ChapterVerseRange"))?,
         })
     }
 }
@@ -190,10 +221,13 @@ impl ParsableSegment for ChapterVerseRange {
         let chapter = ChapterVerseRange::take_number(chars)?;
         ChapterVerseRange::expect_char(chars, ':')?;
         let start_verse = ChapterVerseRange::take_number(chars)?;
+        let start_subverse = ChapterVerseRange::take_subverse(chars);
         ChapterVerseRange::expect_char(chars, '-')?;
         let end_verse = ChapterVerseRange::take_number(chars)?;
+        let end_subverse = ChapterVerseRange::take_subverse(chars);
         ChapterVerseRange::expect_done(chars)?;
 
-        Ok(ChapterVerseRange::new(chapter, start_verse, end_verse))
+        Ok(ChapterVerseRange::new(chapter, start_verse, end_verse)
+            .with_subverses(start_subverse, end_subverse))
     }
 }
@@ -0,0 +1,133 @@
+use crate::segments::{
+    segment::{ChapterlessFormat, Segment},
+    units::parse::{ParsableSegment, SegmentParseMethods},
+    verse_bounds::VerseBounds,
+};
+use serde::{Deserialize, Serialize, de::Visitor};
+use std::{fmt::Display, str::FromStr};
+
+/// - This is the entire book, with no chapter at all
+/// - Ex: `Ruth` in `the book of Ruth`
+/// - It formats as an empty string, so the passage is just the book name
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FullBook;
+
+impl Display for FullBook {
+    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl ChapterlessFormat for FullBook {
+    fn chapterless_format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Serialize for FullBook {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+struct FullBookVisitor;
+
+impl<'de> Visitor<'de> for FullBookVisitor {
+    type Value = FullBook;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("format ''")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        v.parse().map_err(|e| E::custom(e))
+    }
+}
+
+impl<'de> Deserialize<'de> for FullBook {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FullBookVisitor)
+    }
+}
+
+impl FromStr for FullBook {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// - The last chapter isn't known without the book's data, so this ends at the highest chapter
+/// possible
+/// - Don't rely on it: resolve the book to its real chapters with
+/// [`Segment::resolve_full_book`] (or [`Segments::resolve_full_books`](crate::segments::segments::Segments::resolve_full_books))
+impl VerseBounds for FullBook {
+    fn starting_chapter(&self) -> u8 {
+        1
+    }
+
+    fn starting_verse(&self) -> u8 {
+        1
+    }
+
+    fn ending_chapter(&self) -> u8 {
+        u8::MAX
+    }
+
+    fn ending_verse(&self) -> Option<u8> {
+        None
+    }
+}
+
+impl Into<Segment> for FullBook {
+    fn into(self) -> Segment {
+        Segment::FullBook(self)
+    }
+}
+
+impl TryFrom<Segment> for FullBook {
+    type Error = String;
+
+    fn try_from(value: Segment) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Segment::ChapterVerse(_) => Err(format!("Cannot coerce ChapterVerse into FullBook"))?,
+            Segment::ChapterVerseRange(_) => {
+                Err(format!("Cannot coerce ChapterVerseRange into FullBook"))?
+            }
+            Segment::ChapterVerseOnward(_) => {
+                Err(format!("Cannot coerce ChapterVerseOnward into FullBook"))?
+            }
+            Segment::ChapterRange(_) => Err(format!("Cannot coerce ChapterRange into FullBook"))?,
+            Segment::FullChapter(_) => Err(format!("Cannot coerce FullChapter into FullBook"))?,
+            Segment::FullChapterRange(_) => {
+                Err(format!("Cannot coerce FullChapterRange into FullBook"))?
+            }
+            Segment::FullChapterVerseRange(_) => {
+                Err(format!("Cannot coerce FullChapterVerseRange into FullBook"))?
+            }
+            Segment::FullBook(full_book) => full_book,
+        })
+    }
+}
+
+impl ParsableSegment for FullBook {
+    const EXPECTED_FORMAT: &'static str = "";
+
+    fn parse_strict(input: &str) -> Result<Self, String> {
+        let chars = &mut input.chars().peekable();
+
+        FullBook::expect_done(chars)?;
+
+        Ok(FullBook)
+    }
+}
@@ -125,6 +125,9 @@ impl TryFrom<Segment> for FullChapter {
             Segment::ChapterVerseRange(chapter_verse_range) => {
                 FullChapter::new(chapter_verse_range.chapter)
             }
+            Segment::ChapterVerseOnward(chapter_verse_onward) => {
+                FullChapter::new(chapter_verse_onward.chapter)
+            }
             Segment::ChapterRange(_) => {
                 Err(format!("Cannot coerce ChapterRange into FullChapter"))?
             }
@@ -135,6 +138,7 @@ impl TryFrom<Segment> for FullChapter {
             Segment::FullChapterVerseRange(_) => Err(format!(
                 "Cannot coerce FullChapterVerseRange into FullChapter"
             ))?,
+            Segment::FullBook(_) => Err(format!("Cannot coerce FullBook into FullChapter"))?,
         })
     }
 }
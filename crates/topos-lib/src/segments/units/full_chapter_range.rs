@@ -158,6 +158,9 @@ impl TryFrom<Segment> for FullChapterRange {
             Segment::ChapterVerseRange(chapter_verse_range) => {
                 FullChapterRange::new(chapter_verse_range.chapter, chapter_verse_range.chapter)
             }
+            Segment::ChapterVerseOnward(chapter_verse_onward) => {
+                FullChapterRange::new(chapter_verse_onward.chapter, chapter_verse_onward.chapter)
+            }
             Segment::ChapterRange(chapter_range) => {
                 FullChapterRange::new(chapter_range.start.chapter, chapter_range.end.chapter)
             }
@@ -168,6 +171,7 @@ impl TryFrom<Segment> for FullChapterRange {
             Segment::FullChapterVerseRange(_) => Err(format!(
                 "Cannot coerce FullChapterVerseRange into FullChapterRange"
             ))?,
+            Segment::FullBook(_) => Err(format!("Cannot coerce FullBook into FullChapterRange"))?,
         })
     }
 }
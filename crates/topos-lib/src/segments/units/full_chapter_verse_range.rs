@@ -18,13 +18,13 @@ pub struct FullChapterVerseRange {
 
 impl Display for FullChapterVerseRange {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}-{}:{}", self.start, self.end.chapter, self.end.verse)
+        write!(f, "{}-{}", self.start, self.end)
     }
 }
 
 impl ChapterlessFormat for FullChapterVerseRange {
     fn chapterless_format(&self) -> String {
-        format!("{}:{}", self.end.chapter, self.end.verse)
+        self.end.to_string()
     }
 }
 
@@ -101,6 +101,10 @@ impl VerseBounds for FullChapterVerseRange {
     fn ending_verse(&self) -> Option<u8> {
         Some(self.end.verse)
     }
+
+    fn ending_subverse(&self) -> Option<char> {
+        self.end.subverse
+    }
 }
 
 impl PartialOrd for FullChapterVerseRange {
@@ -109,7 +113,8 @@ impl PartialOrd for FullChapterVerseRange {
             self.start
                 .cmp(&other.start)
                 .then(self.end.chapter.cmp(&other.end.chapter))
-                .then(self.end.verse.cmp(&other.end.verse)),
+                .then(self.end.verse.cmp(&other.end.verse))
+                .then(self.end.subverse.cmp(&other.end.subverse)),
         )
     }
 }
@@ -118,10 +123,7 @@ impl FullChapterVerseRange {
     pub fn new(start_chapter: u8, end_chapter: u8, end_verse: u8) -> Self {
         FullChapterVerseRange {
             start: start_chapter,
-            end: ChapterVerse {
-                chapter: end_chapter,
-                verse: end_verse,
-            },
+            end: ChapterVerse::new(end_chapter, end_verse),
         }
     }
 }
@@ -145,6 +147,9 @@ impl TryFrom<Segment> for FullChapterVerseRange {
             Segment::ChapterVerseRange(_) => Err(format!(
                 "Cannot coerce ChapterRange into FullChapterVerseRange"
             ))?,
+            Segment::ChapterVerseOnward(_) => Err(format!(
+                "Cannot coerce ChapterVerseOnward into FullChapterVerseRange"
+            ))?,
             Segment::ChapterRange(_) => Err(format!(
                 "Cannot coerce ChapterRange into FullChapterVerseRange"
             ))?,
@@ -155,6 +160,9 @@ impl TryFrom<Segment> for FullChapterVerseRange {
                 "Cannot coerce FullChapterRange into FullChapterVerseRange"
             ))?,
             Segment::FullChapterVerseRange(full_chapter_verse_range) => full_chapter_verse_range,
+            Segment::FullBook(_) => {
+                Err(format!("Cannot coerce FullBook into FullChapterVerseRange"))?
+            }
         })
     }
 }
@@ -170,8 +178,11 @@ impl ParsableSegment for FullChapterVerseRange {
         let end_chapter = FullChapterVerseRange::take_number(chars)?;
         FullChapterVerseRange::expect_char(chars, ':')?;
         let end_verse = FullChapterVerseRange::take_number(chars)?;
+        let end_subverse = FullChapterVerseRange::take_subverse(chars);
         FullChapterVerseRange::expect_done(chars)?;
 
-        Ok(FullChapterVerseRange::new(chapter, end_chapter, end_verse))
+        let mut segment = FullChapterVerseRange::new(chapter, end_chapter, end_verse);
+        segment.end.subverse = end_subverse;
+        Ok(segment)
     }
 }
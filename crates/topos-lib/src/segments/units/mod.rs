@@ -1,6 +1,8 @@
 pub mod chapter_range;
 pub mod chapter_verse;
+pub mod chapter_verse_onward;
 pub mod chapter_verse_range;
+pub mod full_book;
 pub mod full_chapter;
 pub mod full_chapter_range;
 pub mod full_chapter_verse_range;
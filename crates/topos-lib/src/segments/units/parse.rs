@@ -5,7 +5,12 @@ use std::{
 
 use itertools::Itertools;
 
-use crate::segments::{segment::Segment, segments::Segments, units::chapter_verse::ChapterVerse};
+use crate::segments::{
+    parser::components::{Decimal, Subverse},
+    segment::Segment,
+    segments::Segments,
+    units::chapter_verse::ChapterVerse,
+};
 
 pub(crate) trait SegmentParseMethods: ParsableSegment {
     fn expect_done(chars: &mut Peekable<Chars<'_>>) -> Result<(), String> {
@@ -24,13 +29,25 @@ pub(crate) trait SegmentParseMethods: ParsableSegment {
         }
     }
 
-    /// It must be peekable to not consume the following element
+    /// - It must be peekable to not consume the following element
+    /// - Digits can be from any script [`Decimal::digit_value`] knows
     fn take_number(chars: &mut Peekable<Chars<'_>>) -> Result<u8, String> {
-        chars
-            .peeking_take_while(|c| c.is_numeric())
-            .join("")
-            .parse::<u8>()
-            .map_err(|_| format!("Expected format '{}'", Self::EXPECTED_FORMAT))
+        let digits: Vec<u8> = chars
+            .peeking_take_while(|c| Decimal::digit_value(*c).is_some())
+            .filter_map(Decimal::digit_value)
+            .collect();
+        if digits.is_empty() {
+            Err(format!("Expected format '{}'", Self::EXPECTED_FORMAT))?
+        }
+        digits
+            .iter()
+            .try_fold(0u8, |acc, d| acc.checked_mul(10)?.checked_add(*d))
+            .ok_or_else(|| format!("Expected format '{}'", Self::EXPECTED_FORMAT))
+    }
+
+    /// The optional subverse right after a verse number (`a` in `16a`)
+    fn take_subverse(chars: &mut Peekable<Chars<'_>>) -> Option<char> {
+        chars.next_if(|c| Subverse::is_subverse(*c))
     }
 }
 impl<T: ParsableSegment> SegmentParseMethods for T {}
@@ -11,6 +11,18 @@ pub trait VerseBounds: Copy + Sized + std::fmt::Debug + Into<Segment> {
 
     fn ending_chapter(&self) -> u8;
 
+    /// - Part of the starting verse (`a` in `3:23a-25`)
+    /// - [`None`] means the whole verse
+    fn starting_subverse(&self) -> Option<char> {
+        None
+    }
+
+    /// - Part of the ending verse (`b` in `3:23-25b`)
+    /// - [`None`] means the whole verse
+    fn ending_subverse(&self) -> Option<char> {
+        None
+    }
+
     /// - The verse range starts at 1 when not the starting chapter
     /// - The verse range is unbounded when not the ending chapter
     fn verse_range(&self, chapter: u8) -> (Bound<u8>, Bound<u8>) {
@@ -42,6 +54,15 @@ pub trait VerseBounds: Copy + Sized + std::fmt::Debug + Into<Segment> {
             self.ending_chapter() == other.starting_chapter()
             && self.ending_verse().is_some_and(|ending_verse| ending_verse < other.starting_verse())
         )
+        // or it ends in an earlier part of the verse the other one starts in (`3:23a` and `3:23b`)
+        || (
+            self.ending_chapter() == other.starting_chapter()
+            && self.ending_verse() == Some(other.starting_verse())
+            && matches!(
+                (self.ending_subverse(), other.starting_subverse()),
+                (Some(this), Some(other)) if this < other
+            )
+        )
     }
 
     fn ends_at_or_before(&self, other: &impl VerseBounds) -> bool {
@@ -81,7 +102,18 @@ pub trait VerseBounds: Copy + Sized + std::fmt::Debug + Into<Segment> {
                 // starting chapter is same
                 self.starting_chapter() == other.starting_chapter()
                 // starting verse is before
-                && self.starting_verse() <= other.starting_verse()
+                && (
+                    self.starting_verse() < other.starting_verse()
+                    // or the same verse, and this doesn't start later in it
+                    || (
+                        self.starting_verse() == other.starting_verse()
+                        && match (self.starting_subverse(), other.starting_subverse()) {
+                            (None, _) => true,
+                            (Some(_), None) => false,
+                            (Some(this), Some(other)) => this <= other,
+                        }
+                    )
+                )
             )
         ) && (
             // ending chapter is before
@@ -92,8 +124,16 @@ pub trait VerseBounds: Copy + Sized + std::fmt::Debug + Into<Segment> {
                 self.ending_chapter() == other.ending_chapter()
                 // ending verse is before
                 && match (other.ending_verse(), self.ending_verse()) {
-                    // perform comparison
-                    (Some(other), Some(this)) => other <= this,
+                    // perform comparison, and if it's the same verse, this can't end earlier in it
+                    (Some(other_verse), Some(this_verse)) => {
+                        other_verse < this_verse
+                            || (other_verse == this_verse
+                                && match (self.ending_subverse(), other.ending_subverse()) {
+                                    (None, _) => true,
+                                    (Some(_), None) => false,
+                                    (Some(this), Some(other)) => this >= other,
+                                })
+                    }
                     // other is unbounded, self is bounded => self doesn't contain other
                     // TODO: what about when the user specifies 1 John 1:1-10 and 1 John 1 is found,
                     // since they cover the same range
@@ -178,6 +218,24 @@ mod tests {
         assert_eq!(psg("2:1-2")?.fully_contains(&psg("2")?), false);
         assert_eq!(psg("2")?.fully_contains(&psg("2:1-2")?), true);
 
+        assert_eq!(psg("2:2")?.fully_contains(&psg("2:2a")?), true);
+        assert_eq!(psg("2:2a")?.fully_contains(&psg("2:2")?), false);
+        assert_eq!(psg("2:2a")?.fully_contains(&psg("2:2b")?), false);
+        assert_eq!(psg("2:1b-3a")?.fully_contains(&psg("2:2")?), true);
+
+        Ok(())
+    }
+
+    #[test]
+    fn subverse_overlap() -> AnyResult<()> {
+        let psg = |s: &str| Segment::parse(s);
+
+        assert_eq!(psg("3:23a")?.overlaps_with(&psg("3:23b")?), false);
+        assert_eq!(psg("3:23b")?.overlaps_with(&psg("3:23a")?), false);
+        assert_eq!(psg("3:23a")?.overlaps_with(&psg("3:23")?), true);
+        assert_eq!(psg("3:22-23a")?.overlaps_with(&psg("3:23b-24")?), false);
+        assert_eq!(psg("3:22-23b")?.overlaps_with(&psg("3:23a-24")?), true);
+
         Ok(())
     }
 }
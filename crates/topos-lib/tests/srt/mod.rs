@@ -1,6 +1,6 @@
 use topos_lib::{
     error::AnyResult,
-    matcher::{location::srt::SRTLocation, matcher::BibleMatcher},
+    matcher::{location::srt::SRTLocation, matcher::BibleMatcher, options::MatcherOptions},
 };
 
 #[test]
@@ -11,3 +11,31 @@ fn tdp_c1() -> AnyResult<()> {
     dbg!(results);
     Ok(())
 }
+
+#[test]
+fn spoken_references() -> AnyResult<()> {
+    let srt = "1
+00:00:01,000 --> 00:00:04,000
+Turn with me to John chapter three, verses sixteen through eighteen.
+
+2
+00:00:04,500 --> 00:00:07,000
+And then first John two one, and Romans 8:28.
+";
+    let matcher = BibleMatcher::default().with_options(MatcherOptions::default().with_spoken());
+    let results = matcher.search::<SRTLocation>(srt)?;
+
+    let found: Vec<(u8, String)> = results
+        .iter()
+        .map(|m| (*m.psg.book, m.psg.segments.to_string()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (43, "3:16-18".to_string()),
+            (62, "2:1".to_string()),
+            (45, "8:28".to_string()),
+        ]
+    );
+    Ok(())
+}
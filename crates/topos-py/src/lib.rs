@@ -1,5 +1,5 @@
 use pyo3::prelude::*;
-use topos_lib::matcher::matcher::BibleMatcher;
+use topos_lib::matcher::{location::line_col::LineColLocation, matcher::BibleMatcher};
 
 /// Formats the sum of two numbers as string.
 #[pyfunction]
@@ -22,10 +22,39 @@ fn search(input: &str) -> PyResult<String> {
         .join(" | "))
 }
 
+/// The OSIS reference of every match (`John.3.16-John.3.18`)
+#[pyfunction]
+fn to_osis(input: &str) -> PyResult<Vec<String>> {
+    let m = BibleMatcher::default();
+    Ok(m.search::<LineColLocation>(input)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|r| match &r.range {
+            Some(range) => m.data().format_osis_range(range),
+            None => m.data().format_osis(&r.psg),
+        })
+        .collect())
+}
+
+/// An OSIS reference (`John.3.16`, `Gen.50.26-Exod.1.7`), written out (`John 3:16`)
+#[pyfunction]
+fn from_osis(osis: &str) -> PyResult<Option<String>> {
+    let m = BibleMatcher::default();
+    let data = m.data();
+    Ok(match data.parse_osis_range(osis) {
+        Some(range) => data.format_range(&range),
+        None => data
+            .parse_osis(osis)
+            .and_then(|psg| data.format_passage(&psg)),
+    })
+}
+
 /// A Python module implemented in Rust.
 #[pymodule]
 fn topos(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(sum_as_string, m)?)?;
     m.add_function(wrap_pyfunction!(search, m)?)?;
+    m.add_function(wrap_pyfunction!(to_osis, m)?)?;
+    m.add_function(wrap_pyfunction!(from_osis, m)?)?;
     Ok(())
 }
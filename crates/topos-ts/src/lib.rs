@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use topos_lib::matcher::matcher::BibleMatcher;
+use topos_lib::matcher::{location::line_col::LineColLocation, matcher::BibleMatcher};
 use wasm_bindgen::prelude::*;
 
 static BIBLE: Lazy<BibleMatcher> = Lazy::new(|| BibleMatcher::default());
@@ -33,3 +33,27 @@ pub fn autocomplete(input: &str) -> Option<Vec<String>> {
             .collect(),
     )
 }
+
+/// The OSIS reference of every match (`John.3.16-John.3.18`)
+#[wasm_bindgen]
+pub fn to_osis(input: &str) -> Vec<String> {
+    let m = &*BIBLE;
+    m.search::<LineColLocation>(input)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|r| match &r.range {
+            Some(range) => m.data().format_osis_range(range),
+            None => m.data().format_osis(&r.psg),
+        })
+        .collect()
+}
+
+/// An OSIS reference (`John.3.16`, `Gen.50.26-Exod.1.7`), written out (`John 3:16`)
+#[wasm_bindgen]
+pub fn from_osis(osis: &str) -> Option<String> {
+    let data = BIBLE.data();
+    match data.parse_osis_range(osis) {
+        Some(range) => data.format_range(&range),
+        None => data.format_passage(&data.parse_osis(osis)?),
+    }
+}